@@ -0,0 +1 @@
+cargo run -- --local
\ No newline at end of file
@@ -0,0 +1 @@
+cargo run -- --synctest 7
\ No newline at end of file
@@ -1,22 +1,22 @@
 use std::net::SocketAddr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 
 use bevy::{ecs::schedule::ShouldRun, prelude::*};
 use bevy_ggrs::{GGRSApp, GGRSPlugin, Rollback, RollbackIdProvider};
-use ggrs::{GameInput, P2PSession, PlayerType};
+use ggrs::{GameInput, P2PSession, PlayerHandle, PlayerType, SyncTestSession};
 
 use std::collections::HashMap;
 
+mod sim;
 mod systems;
+use crate::sim::*;
 use crate::systems::*;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Reflect, Component)]
 pub enum GameState {
     Setup,
     Fighting,
-    Reset,
-    HitStop,
 }
 
 impl Default for GameState {
@@ -30,7 +30,8 @@ pub struct TextureAtlasDictionary {
     pub animation_handles: HashMap<String, Handle<TextureAtlas>>,
     pub debug_hit_box_texture: Handle<ColorMaterial>,
     pub debug_hurt_box_texture: Handle<ColorMaterial>,
-    pub cloud_image: Handle<ColorMaterial>,
+    //Keyed by the animation name in the projectile's move data
+    pub projectile_images: HashMap<String, Handle<ColorMaterial>>,
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
@@ -41,71 +42,97 @@ const FPS: u32 = 60;
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // read cmd line arguments
     let opt = Opt::from_args();
-    let num_players = opt.players.len();
-    assert!(num_players > 0);
-
-    let mut p2p_sess = P2PSession::new(2, INPUT_SIZE, opt.local_port)?;
-    p2p_sess.set_sparse_saving(true)?;
-    p2p_sess.set_fps(FPS).expect("Invalid fps");
+    let input_bindings = InputBindings::from_file(&opt.bindings)?;
 
     let collider_both = Path::new("./assets/hitboxes/character_1.json");
-    App::new()
-        .insert_resource(WindowDescriptor {
-            title: "MKP Fighting".to_string(),
-            width: 1280.,
-            height: 700.,
-            vsync: true,
-            ..Default::default()
-        })
-        .add_plugins(DefaultPlugins)
-        .add_plugin(GGRSPlugin)
-        .insert_resource(ShouldRenderHitBoxes::new(opt.render_hitboxes))
-        .insert_resource(opt)
-        .insert_resource(HitStopTimer::new(2))
-        .add_state(GameState::Setup)
-        .insert_resource(RestartSystemState::default())
-        .insert_resource(LocalId::default())
-        .insert_resource(ColliderSetComponent::from_file(&collider_both))
-        .insert_resource(InputEvents::default())
-        .insert_resource(TextureAtlasDictionary::default())
-        .add_startup_system(start_p2p_session)
-        .add_startup_system(match_setup)
-        .add_startup_system(hit_box_setup_system)
-        .register_rollback_type::<Transform>()
-        .register_rollback_type::<PlayerState>()
-        .register_rollback_type::<GameState>()
-        .register_rollback_type::<SpriteTimer>()
-        .with_input_system(keyboard_input_system.system())
-        //Any of the systems that we wanted effected by Rollback
-        //To be honest, there is some guess work in there
-        .with_rollback_schedule(
-            Schedule::default().with_stage(
-                ROLLBACK_DEFAULT,
-                SystemStage::single_threaded()
-                    .with_run_criteria(game_is_fighting_state)
-                    .with_system(collision_system)
-                    .with_system(player_state_system)
-                    .with_system(player_movement_system)
-                    .with_system(sprite_system)
-                    .with_system(round_timer_system),
-            ),
-        )
-        //Any system we don't want in rollback, but do want fun during the fighting state
-        .add_system_set(
-            SystemSet::new()
+    let move_table = Path::new("./assets/hitboxes/character_1_moves.json");
+    let mut app = App::new();
+    app.insert_resource(WindowDescriptor {
+        title: "MKP Fighting".to_string(),
+        width: 1280.,
+        height: 700.,
+        vsync: true,
+        ..Default::default()
+    })
+    .add_plugins(DefaultPlugins)
+    .add_plugin(GGRSPlugin)
+    .insert_resource(ShouldRenderHitBoxes::new(opt.render_hitboxes))
+    .insert_resource(DesyncDetector::new(opt.local_port, &opt.players)?)
+    .insert_resource(InputRecorder::new(opt.record.clone()))
+    .insert_resource(InputReplay::from_file(opt.replay.as_deref()))
+    .insert_resource(input_bindings.clone())
+    .insert_resource(GamepadAssignments::default())
+    .insert_resource(SocdState::default())
+    .insert_resource(InputFormatCheck::default())
+    .add_state(GameState::Setup)
+    .insert_resource(ColliderSetComponent::from_file(&collider_both))
+    .insert_resource(MoveTable::from_file(&move_table))
+    .insert_resource(TextureAtlasDictionary::default())
+    .add_startup_system(match_setup)
+    .add_startup_system(hit_box_setup_system)
+    .register_rollback_type::<FightSim>()
+    .register_rollback_type::<GameState>()
+    .with_input_system(local_input_system.system())
+    //Any of the systems that we wanted effected by Rollback
+    //To be honest, there is some guess work in there
+    .with_rollback_schedule(
+        Schedule::default().with_stage(
+            ROLLBACK_DEFAULT,
+            SystemStage::single_threaded()
                 .with_run_criteria(game_is_fighting_state)
-                .with_system(screen_side_system)
-                .with_system(health_system_ui)
-                .with_system(hitbox_debug_system),
-        )
-        .add_system_set(
-            SystemSet::new()
-                .label(RestartSystem)
-                .with_run_criteria(game_is_reset_state)
-                .with_system(restart_system),
-        )
-        .with_p2p_session(p2p_sess)
-        .run();
+                .with_system(fight_sim_system),
+        ),
+    )
+    //Any system we don't want in rollback, but do want fun during the fighting state
+    .add_system_set(
+        SystemSet::new()
+            .with_run_criteria(game_is_fighting_state)
+            .with_system(player_render_system)
+            .with_system(projectile_system)
+            .with_system(combo_counter_system)
+            .with_system(health_system_ui)
+            .with_system(meter_system_ui)
+            .with_system(round_timer_system)
+            .with_system(restart_system)
+            .with_system(hitbox_debug_system),
+    )
+    //Keeps watching for desyncs no matter what state the game is in
+    .add_system(desync_detection_system)
+    .add_system(input_recording_system)
+    .add_system(gamepad_connection_system)
+    .add_system(input_format_check_system);
+
+    match opt.synctest {
+        //Both players are fed from this machine, and GGRS rolls back and resimulates every frame,
+        //comparing the checksums of the two runs
+        Some(check_distance) => {
+            input_bindings.require_players(2)?;
+            let sync_sess = SyncTestSession::new(2, INPUT_SIZE, check_distance)?;
+            app.insert_resource(LocalId::all_local(2))
+                .with_synctest_session(sync_sess);
+        }
+        //A check distance of zero never rolls back, so this is just both players stepping on one machine
+        None if opt.local => {
+            input_bindings.require_players(2)?;
+            let local_sess = SyncTestSession::new(2, INPUT_SIZE, 0)?;
+            app.insert_resource(LocalId::all_local(2))
+                .with_synctest_session(local_sess);
+        }
+        None => {
+            let num_players = opt.players.len();
+            assert!(num_players > 0);
+            input_bindings.require_players(1)?;
+
+            let mut p2p_sess = P2PSession::new(2, INPUT_SIZE, opt.local_port)?;
+            p2p_sess.set_sparse_saving(true)?;
+            p2p_sess.set_fps(FPS).expect("Invalid fps");
+            app.insert_resource(LocalId::default())
+                .add_startup_system(start_p2p_session)
+                .with_p2p_session(p2p_sess);
+        }
+    }
+
+    app.insert_resource(opt).run();
     Ok(())
 }
 
@@ -116,131 +143,13 @@ pub fn game_is_fighting_state(state: Res<State<GameState>>) -> ShouldRun {
     match state.current() {
         GameState::Setup => ShouldRun::No,
         GameState::Fighting => ShouldRun::Yes,
-        GameState::HitStop => ShouldRun::Yes,
-        GameState::Reset => ShouldRun::No,
-    }
-}
-
-// Only let the Reset System set run when
-// our game state is Reset, this is a hack to deal with some
-// problems with how Bevy_ggrs handle schedules
-pub fn game_is_reset_state(state: Res<State<GameState>>) -> ShouldRun {
-    match state.current() {
-        GameState::Setup => ShouldRun::No,
-        GameState::Fighting => ShouldRun::No,
-        GameState::HitStop => ShouldRun::No,
-        GameState::Reset => ShouldRun::Yes,
-    }
-}
-
-#[derive(Copy, Clone, Component, Default, Reflect)]
-pub struct SpriteTimer {
-    total_frames: usize,
-    current_frame: usize,
-    finished: bool,
-}
-
-impl SpriteTimer {
-    pub fn new(total_frames: usize) -> SpriteTimer {
-        SpriteTimer {
-            total_frames,
-            current_frame: 0,
-            finished: false,
-        }
-    }
-
-    pub fn tick(&mut self) {
-        self.finished = false;
-        self.current_frame += 1;
-        if self.current_frame == self.total_frames {
-            self.finished = true;
-            self.current_frame = 0;
-        }
-    }
-
-    pub fn finished(&mut self) -> bool {
-        self.finished
-    }
-
-    pub fn reset(&mut self) {
-        self.current_frame = 0;
-        self.finished = false;
-    }
-}
-
-struct HitStopTimer {
-    length: usize,
-    current: usize,
-}
-
-impl HitStopTimer {
-    pub fn new(length: usize) -> HitStopTimer {
-        HitStopTimer { length, current: 0 }
-    }
-}
-
-fn sprite_system(
-    texture_atlases: Res<Assets<TextureAtlas>>,
-    mut query: Query<(
-        &mut SpriteTimer,
-        &mut TextureAtlasSprite,
-        &Handle<TextureAtlas>,
-        &mut PlayerState,
-        &ScreenSideEnum,
-    )>,
-    mut state: ResMut<State<GameState>>,
-    mut hit_stop_timer: ResMut<HitStopTimer>,
-) {
-    if *state.current() == GameState::HitStop {
-        hit_stop_timer.current += 1;
-        if hit_stop_timer.current == hit_stop_timer.length {
-            hit_stop_timer.current = 0;
-            state.set(GameState::Fighting).unwrap();
-        }
-        return;
-    }
-    for (mut timer, mut sprite, texture_atlas_handle, mut player_state, &screen_side) in
-        query.iter_mut()
-    {
-        //Update the timer
-        timer.tick();
-        let texture_atlas = texture_atlases.get(texture_atlas_handle).unwrap();
-        //An odd place to do this, but ok for now, make sure the sprite is facing the right direciton
-        match screen_side {
-            ScreenSideEnum::Left => {
-                sprite.flip_x = false;
-            }
-            ScreenSideEnum::Right => {
-                sprite.flip_x = true;
-            }
-        }
-
-        // Time to change the sprite
-        if timer.finished() {
-            let next = ((player_state.current_sprite_index as usize + 1)
-                % texture_atlas.textures.len()) as u32;
-            //As we start it at 0, we should let the system know "we have finished playing a full animation cycle, who wants next"
-            if next == 0 {
-                let desired_state = player_state.animation_finished();
-                if desired_state == player_state.player_state {
-                    player_state.reset_state();
-                } else {
-                    player_state.reset_state();
-                    player_state.set_player_state_to_transition(desired_state);
-                }
-                continue;
-            }
-            //Make sure that the spirte, the characters idea of which sprite index we are on are in the same place
-            sprite.index = next;
-            player_state.current_sprite_index = next as usize;
-        }
     }
 }
 
 // structopt will read command line parameters for u
 #[derive(StructOpt)]
 struct Opt {
-    #[structopt(short, long)]
+    #[structopt(short, long, default_value = "7000")]
     local_port: u16,
     #[structopt(short, long)]
     players: Vec<String>,
@@ -248,11 +157,48 @@ struct Opt {
     render_hitboxes: bool,
     #[structopt(short, long)]
     spectators: Vec<SocketAddr>,
+    // which keys each local player uses
+    #[structopt(
+        long,
+        parse(from_os_str),
+        default_value = "./assets/config/bindings.json"
+    )]
+    bindings: PathBuf,
+    // both players on this machine, player 2 on the second key set
+    #[structopt(long)]
+    local: bool,
+    // run a local GGRS SyncTest session that rolls back this many frames every frame
+    #[structopt(long)]
+    synctest: Option<u32>,
+    // feed both players from a recording instead of the keyboard, quitting when it runs out
+    #[structopt(long, parse(from_os_str))]
+    replay: Option<PathBuf>,
+    // write both players inputs to this file so the match can be replayed later
+    #[structopt(long, parse(from_os_str))]
+    record: Option<PathBuf>,
 }
 
 #[derive(Default, Component)]
 pub struct LocalId {
     pub id: usize,
+    // every handle whose input comes from this machine, in the order their key sets are handed out
+    pub handles: Vec<PlayerHandle>,
+}
+
+impl LocalId {
+    pub fn all_local(num_players: usize) -> LocalId {
+        LocalId {
+            id: 0,
+            handles: (0..num_players).collect(),
+        }
+    }
+
+    pub fn local_player_index(&self, handle: PlayerHandle) -> usize {
+        self.handles
+            .iter()
+            .position(|local_handle| *local_handle == handle)
+            .unwrap_or(0)
+    }
 }
 
 fn start_p2p_session(
@@ -269,6 +215,7 @@ fn start_p2p_session(
         if player_addr == "localhost" {
             p2p_sess.add_player(PlayerType::Local, i).unwrap();
             local_handle = i;
+            local_id.handles.push(i);
             if i == 0 {
                 local_id.id = 0;
             }
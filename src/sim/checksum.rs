@@ -0,0 +1,37 @@
+use std::hash::{Hash, Hasher};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+//FNV-1a, used instead of the std hasher because its output is pinned down forever,
+//so two different builds of the game will always agree on what a state hashes to
+pub struct ChecksumHasher {
+    state: u64,
+}
+
+impl Default for ChecksumHasher {
+    fn default() -> ChecksumHasher {
+        ChecksumHasher {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl Hasher for ChecksumHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+pub fn checksum<T: Hash>(value: &T) -> u64 {
+    let mut hasher = ChecksumHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::sim::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+//How long any keyframe without its own timing stays up for
+pub const DEFAULT_FRAME_HOLD: usize = 3;
+
+//How many frames one keyframe of an animation is held for, and which drawing in the sprite sheet it shows
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FrameTiming {
+    pub sprite: usize,
+    pub hold: usize,
+}
+
+//Each animation is a list of keyframes, every keyframe has its own colliders
+#[derive(Default, Deserialize, Component)]
+pub struct ColliderSetComponent {
+    pub colliders: HashMap<String, Vec<Vec<Collider>>>,
+    //Animations missing from here hold each keyframe for DEFAULT_FRAME_HOLD and draw the sprite with the same index
+    #[serde(default)]
+    pub frame_timings: HashMap<String, Vec<FrameTiming>>,
+}
+
+impl ColliderSetComponent {
+    pub fn from_file(path: &Path) -> ColliderSetComponent {
+        let file_contents = fs::read_to_string(path).unwrap();
+        ColliderSetComponent::from_json(&file_contents)
+    }
+
+    //Every timed animation needs a timing for each keyframe, and every keyframe has to be shown for at least a frame
+    pub fn from_json(json: &str) -> ColliderSetComponent {
+        let deserialized: ColliderSetComponent = serde_json::from_str(json).unwrap();
+        for (animation_name, timings) in deserialized.frame_timings.iter() {
+            let keyframes = deserialized
+                .colliders
+                .get(animation_name)
+                .map(|frames| frames.len());
+            assert!(
+                keyframes == Some(timings.len()),
+                "{} has {} frame timings but {:?} keyframes of colliders",
+                animation_name,
+                timings.len(),
+                keyframes
+            );
+            for (keyframe, timing) in timings.iter().enumerate() {
+                assert!(
+                    timing.hold > 0,
+                    "{} holds keyframe {} for 0 frames",
+                    animation_name,
+                    keyframe
+                );
+            }
+        }
+        return deserialized;
+    }
+
+    fn frame_timing(&self, animation_name: &str, keyframe: usize) -> FrameTiming {
+        match self.frame_timings.get(animation_name) {
+            Some(timings) => timings[keyframe],
+            None => FrameTiming {
+                sprite: keyframe,
+                hold: DEFAULT_FRAME_HOLD,
+            },
+        }
+    }
+
+    //In frames, from the first frame of the animation until it loops
+    pub fn animation_length(&self, animation_name: &str) -> usize {
+        (0..self.colliders[animation_name].len())
+            .map(|keyframe| self.frame_timing(animation_name, keyframe).hold)
+            .sum()
+    }
+
+    pub fn keyframe_at(&self, animation_name: &str, animation_frame: usize) -> usize {
+        let keyframes = self.colliders[animation_name].len();
+        let mut keyframe_end = 0;
+        for keyframe in 0..keyframes {
+            keyframe_end += self.frame_timing(animation_name, keyframe).hold;
+            if animation_frame < keyframe_end {
+                return keyframe;
+            }
+        }
+        keyframes - 1
+    }
+
+    pub fn sprite_index(&self, animation_name: &str, animation_frame: usize) -> usize {
+        let keyframe = self.keyframe_at(animation_name, animation_frame);
+        self.frame_timing(animation_name, keyframe).sprite
+    }
+
+    pub fn colliders_at(&self, animation_name: &str, animation_frame: usize) -> &Vec<Collider> {
+        let keyframe = self.keyframe_at(animation_name, animation_frame);
+        &self.colliders[animation_name][keyframe]
+    }
+
+    pub fn frame_colliders(&self, player_state: &PlayerState) -> &Vec<Collider> {
+        let animation_name = player_state.player_state.to_string();
+        self.colliders_at(&animation_name, player_state.animation_frame)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Component)]
+pub enum ColliderType {
+    HitBox,
+    HurtBox,
+    //Grabs the HitBox of the other fighter, it never strikes or gets struck
+    ThrowBox,
+}
+
+//How a collider is written in the hitbox file, in pixels
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct ColliderDefinition {
+    pub offset: Vec3,
+    pub dimension: Vec2,
+    pub collider_type: ColliderType,
+}
+
+//A collider as the simulation sees it, in subpixels
+#[derive(Copy, Clone, Debug, Deserialize, Component)]
+#[serde(from = "ColliderDefinition")]
+pub struct Collider {
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub collider_type: ColliderType,
+}
+
+impl From<ColliderDefinition> for Collider {
+    fn from(definition: ColliderDefinition) -> Collider {
+        Collider {
+            offset_x: to_fixed(definition.offset.x),
+            offset_y: to_fixed(definition.offset.y),
+            width: to_fixed(definition.dimension.x),
+            height: to_fixed(definition.dimension.y),
+            collider_type: definition.collider_type,
+        }
+    }
+}
+
+impl Collider {
+    //Where the center of this collider is for a player standing at position, facing away from screen_side
+    pub fn center(&self, position: Position, screen_side: ScreenSideEnum) -> Position {
+        let mut offset_x = self.offset_x;
+        match screen_side {
+            ScreenSideEnum::Right => {
+                offset_x = -offset_x;
+            }
+            _ => {}
+        }
+        Position::new(position.x + offset_x, position.y + self.offset_y)
+    }
+
+    //Throw boxes only ever grab, and a hurt box only strikes while its move is active
+    pub fn can_collide(&self, move_is_active: bool) -> bool {
+        match self.collider_type {
+            ColliderType::HitBox => true,
+            ColliderType::HurtBox => move_is_active,
+            ColliderType::ThrowBox => false,
+        }
+    }
+
+    pub fn overlaps(&self, center: Position, other: &Collider, other_center: Position) -> bool {
+        //Compare doubled distances against the summed sizes, so we never have to halve anything
+        (center.x - other_center.x).abs() * 2 < self.width + other.width
+            && (center.y - other_center.y).abs() * 2 < self.height + other.height
+    }
+}
+
+//Returns how many frames of hit stop the strikes that connected this frame ask for, if any did
+pub fn resolve_collisions(
+    collider_boxes: &ColliderSetComponent,
+    move_table: &MoveTable,
+    fighter_1: &mut Fighter,
+    fighter_2: &mut Fighter,
+) -> Option<usize> {
+    if resolve_grabs(collider_boxes, move_table, fighter_1, fighter_2) {
+        return None;
+    }
+
+    fighter_1.player_state.is_colliding = false;
+    fighter_2.player_state.is_colliding = false;
+
+    //A hurt box only exists on the active frames of the move it belongs to,
+    //one left over from startup or recovery can not strike, parry or get in the way of a strike
+    let is_active_1 = move_table.active_move(&fighter_1.player_state).is_some();
+    let is_active_2 = move_table.active_move(&fighter_2.player_state).is_some();
+    let p1_colliders = collider_boxes
+        .frame_colliders(&fighter_1.player_state)
+        .iter()
+        .filter(|collider| collider.can_collide(is_active_1));
+    let mut fighter_1_is_struck = false;
+    let mut fighter_2_is_struck = false;
+    let mut is_parry = false;
+    let mut is_bounce = false;
+
+    for collider_1 in p1_colliders {
+        let p2_colliders = collider_boxes
+            .frame_colliders(&fighter_2.player_state)
+            .iter()
+            .filter(|collider| collider.can_collide(is_active_2));
+        for collider_2 in p2_colliders {
+            let collision = collider_1.overlaps(
+                collider_1.center(fighter_1.position, fighter_1.screen_side),
+                collider_2,
+                collider_2.center(fighter_2.position, fighter_2.screen_side),
+            );
+            if collision {
+                match (collider_1.collider_type, collider_2.collider_type) {
+                    (ColliderType::HitBox, ColliderType::HurtBox) => fighter_1_is_struck = true,
+                    (ColliderType::HurtBox, ColliderType::HitBox) => fighter_2_is_struck = true,
+                    (ColliderType::HitBox, ColliderType::HitBox) => is_bounce = true,
+                    _ => is_parry = true,
+                }
+            }
+        }
+    }
+
+    //If we have any collision there are three possible outcomes we care about
+    //1. Two hurt boxes have hit, this is a "parry", meaning that they bounce off each other
+    //2. At least 1 hurt box has hit a hit box, we need to do damage, and sent that player into the taken hit state
+    //   unless they are holding back to block it, then it is chip damage and blockstun instead.
+    //   Both fighters can be struck on the same frame, then the hits trade
+    //3. Only hit box collisions, just means we need to handle bumping and pushing
+    if is_parry {
+        return None;
+    }
+    let mut hit_stop = None;
+    if fighter_1_is_struck {
+        hit_stop = hit_stop.max(strike(move_table, fighter_2, fighter_1));
+    }
+    if fighter_2_is_struck {
+        hit_stop = hit_stop.max(strike(move_table, fighter_1, fighter_2));
+    }
+    if hit_stop.is_some() {
+        return hit_stop;
+    }
+
+    if is_bounce {
+        let player_state_1 = &mut fighter_1.player_state;
+        let player_state_2 = &mut fighter_2.player_state;
+        player_state_1.is_colliding = true;
+        player_state_2.is_colliding = true;
+
+        match player_state_1.player_state {
+            PlayerStateEnum::Idle => {
+                player_state_1.x_velocity = player_state_2.x_velocity * 2;
+            }
+            PlayerStateEnum::HeavyAttack => {}
+            _ => {}
+        }
+        match player_state_2.player_state {
+            PlayerStateEnum::Idle => {
+                player_state_2.x_velocity = player_state_1.x_velocity * 2;
+            }
+            PlayerStateEnum::HeavyAttack => {}
+            _ => {}
+        }
+    }
+    None
+}
+
+//Each move only connects once, however many of its active frames overlap
+fn strike(move_table: &MoveTable, attacker: &mut Fighter, defender: &mut Fighter) -> Option<usize> {
+    let attack = move_table.active_move(&attacker.player_state)?;
+    if attacker.player_state.move_connected || defender.player_state.can_take_a_hit() == false {
+        return None;
+    }
+    let outcome = defender.take_strike(attack)?;
+    attacker.player_state.move_connected = true;
+    attacker.meter.gain(attack.attacker_meter(outcome));
+    Some(attack.hitstop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shipped_colliders() -> serde_json::Value {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/hitboxes/character_1.json");
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn shipped_colliders_load() {
+        let collider_set = ColliderSetComponent::from_json(&shipped_colliders().to_string());
+        assert_eq!(collider_set.animation_length("LightAttack"), 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Throw holds keyframe 1 for 0 frames")]
+    fn zero_hold_is_rejected() {
+        let mut colliders = shipped_colliders();
+        colliders["frame_timings"]["Throw"][1]["hold"] = serde_json::json!(0);
+        ColliderSetComponent::from_json(&colliders.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "LightAttack has 2 frame timings")]
+    fn missing_frame_timing_is_rejected() {
+        let mut colliders = shipped_colliders();
+        colliders["frame_timings"]["LightAttack"]
+            .as_array_mut()
+            .unwrap()
+            .pop();
+        ColliderSetComponent::from_json(&colliders.to_string());
+    }
+}
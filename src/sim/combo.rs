@@ -0,0 +1,54 @@
+use crate::sim::*;
+
+//Every hit already in a combo takes this many percent off the damage of the next one
+const DAMAGE_SCALING_PER_HIT: usize = 10;
+//However long the combo gets, a hit never does less than this percent of its damage
+const MINIMUM_DAMAGE_SCALING: usize = 30;
+//How many juggle points of moves can land on a fighter in the air before they fall out of the combo
+pub const JUGGLE_POINT_LIMIT: usize = 3;
+//How far up a fighter hit in the air by a move that does not launch is knocked, in subpixels
+pub const JUGGLE_POP_SPEED: i32 = 8 * SUBPIXELS_PER_PIXEL;
+
+//The combo a fighter is stuck in, it is counted on the one taking the hits
+#[derive(Copy, Clone, Debug, Default, Hash)]
+pub struct Combo {
+    pub hits: usize,
+    pub juggle_points: usize,
+}
+
+impl Combo {
+    pub fn reset(&mut self) {
+        self.hits = 0;
+        self.juggle_points = 0;
+    }
+
+    pub fn scale_damage(&self, damage: usize) -> usize {
+        let scaling = 100usize
+            .saturating_sub(self.hits * DAMAGE_SCALING_PER_HIT)
+            .max(MINIMUM_DAMAGE_SCALING);
+        (damage * scaling / 100).max(1)
+    }
+
+    pub fn can_juggle(&self) -> bool {
+        self.juggle_points < JUGGLE_POINT_LIMIT
+    }
+}
+
+impl PlayerState {
+    pub fn is_juggled(&self) -> bool {
+        self.player_state == PlayerStateEnum::Juggle || self.player_state == PlayerStateEnum::AirHit
+    }
+}
+
+impl Fighter {
+    pub fn is_airborne(&self) -> bool {
+        self.position.y > FLOOR_HEIGHT
+    }
+
+    //Once the fighter taking the hits is able to act again the combo is over
+    pub fn end_combo_if_recovered(&mut self) {
+        if self.player_state.is_in_hit_stun() == false && self.player_state.is_juggled() == false {
+            self.combo.reset();
+        }
+    }
+}
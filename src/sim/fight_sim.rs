@@ -0,0 +1,281 @@
+use crate::sim::*;
+use bevy::prelude::*;
+
+pub const PLAYER_1_START_X: i32 = -120 * SUBPIXELS_PER_PIXEL;
+pub const PLAYER_2_START_X: i32 = 120 * SUBPIXELS_PER_PIXEL;
+pub const ROUND_LENGTH_IN_SECONDS: usize = 60;
+//Once a round is over everything freezes while the blinds close, the round is reset behind them,
+//then they open again before anyone can move
+pub const ROUND_BLACKOUT_FRAMES: usize = 180;
+pub const ROUND_RESET_FRAMES: usize = ROUND_BLACKOUT_FRAMES * 2;
+
+//The whole of a match, with no knowledge of windows, sprites or textures.
+//Bevy only ever steps this and mirrors what it finds into the things it draws
+#[derive(Clone, Debug, Default, Hash, Reflect, Component)]
+#[reflect_value(Component, Hash)]
+pub struct FightSim {
+    pub frame: u32,
+    pub players: [Fighter; 2],
+    pub round_timer: RoundTimer,
+    pub hit_stop_timer: HitStopTimer,
+    //Oldest first, so they always collide in the same order
+    pub projectiles: Vec<Projectile>,
+    //How far into the reset between rounds we are, 0 while the round is being fought.
+    //It is part of the sim so a round ended by a misprediction is rolled back like anything else
+    pub round_reset_frame: usize,
+}
+
+impl FightSim {
+    pub fn new() -> FightSim {
+        FightSim {
+            frame: 0,
+            players: [
+                Fighter::new(
+                    0,
+                    Position::new(PLAYER_1_START_X, FLOOR_HEIGHT),
+                    ScreenSideEnum::Left,
+                ),
+                Fighter::new(
+                    1,
+                    Position::new(PLAYER_2_START_X, FLOOR_HEIGHT),
+                    ScreenSideEnum::Right,
+                ),
+            ],
+            round_timer: RoundTimer::new(ROUND_LENGTH_IN_SECONDS),
+            hit_stop_timer: HitStopTimer::default(),
+            projectiles: Vec::new(),
+            round_reset_frame: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.players[0].hard_reset(
+            Position::new(PLAYER_1_START_X, FLOOR_HEIGHT),
+            ScreenSideEnum::Left,
+        );
+        self.players[1].hard_reset(
+            Position::new(PLAYER_2_START_X, FLOOR_HEIGHT),
+            ScreenSideEnum::Right,
+        );
+        self.round_timer.reset();
+        self.hit_stop_timer.reset();
+        self.projectiles.clear();
+    }
+
+    //Everything in here is integers, so this is identical on every machine that simulated the same inputs
+    pub fn checksum(&self) -> u64 {
+        checksum(self)
+    }
+
+    pub fn round_is_over(&self) -> bool {
+        self.round_timer.finished() || self.players.iter().any(|p| p.health.health == 0)
+    }
+
+    //Advance the match by exactly one frame
+    pub fn step(
+        &mut self,
+        collider_boxes: &ColliderSetComponent,
+        move_table: &MoveTable,
+        inputs: [InputEvents; 2],
+    ) {
+        self.frame += 1;
+        if self.round_reset_frame > 0 || self.round_is_over() {
+            self.step_round_reset();
+            return;
+        }
+        if self.hit_stop_timer.running() {
+            self.hit_stop_timer.tick();
+            let hit_stop_over = self.hit_stop_timer.running() == false;
+            for (fighter, input) in self.players.iter_mut().zip(inputs.iter()) {
+                fighter.buffer_input(input, move_table);
+                if hit_stop_over {
+                    fighter.is_shaking = false;
+                }
+            }
+            return;
+        }
+
+        //The clock stops with everything else during hit stop
+        self.round_timer.tick();
+        self.update_screen_sides();
+
+        let (player_1, player_2) = self.players.split_at_mut(1);
+        //The rest of this frame plays out so the defender is put into their hit state,
+        //then neither fighter moves until the hit stop is over
+        let strike_hit_stop = resolve_collisions(
+            collider_boxes,
+            move_table,
+            &mut player_1[0],
+            &mut player_2[0],
+        );
+        let projectile_hit_stop = resolve_projectile_collisions(
+            collider_boxes,
+            move_table,
+            &mut self.projectiles,
+            &mut self.players,
+        );
+        if let Some(hit_stop) = strike_hit_stop.max(projectile_hit_stop) {
+            self.hit_stop_timer.start(hit_stop);
+        }
+
+        for projectile in self.projectiles.iter_mut() {
+            projectile.advance();
+        }
+        self.projectiles
+            .retain(|projectile| projectile.is_expired(move_table) == false);
+
+        let is_attacking = [
+            self.players[0].player_state.is_attacking(),
+            self.players[1].player_state.is_attacking(),
+        ];
+        for (index, (fighter, input)) in self.players.iter_mut().zip(inputs.iter()).enumerate() {
+            fighter.player_state.has_projectile = self
+                .projectiles
+                .iter()
+                .any(|projectile| projectile.owner == index);
+            if fighter.health.health == 0 {
+                fighter
+                    .player_state
+                    .set_player_state_to_transition(PlayerStateEnum::Death);
+            }
+            fighter.update_state(input, is_attacking[1 - index], move_table);
+            fighter.apply_movement();
+            if let Some(projectile) = fighter.projectile_to_spawn(move_table) {
+                self.projectiles.push(projectile);
+            }
+            //A super freezes the whole match on its first frame, the same way hit stop does
+            if let Some(super_freeze) = fighter.super_freeze_to_start(move_table) {
+                self.hit_stop_timer.start(super_freeze);
+            }
+            fighter.advance_animation(collider_boxes);
+        }
+
+        //Either of them could be the one throwing
+        let (player_1, player_2) = self.players.split_at_mut(1);
+        let throw_hit_stop = resolve_throw(move_table, &mut player_1[0], &mut player_2[0])
+            .or_else(|| resolve_throw(move_table, &mut player_2[0], &mut player_1[0]));
+        if let Some(hit_stop) = throw_hit_stop {
+            self.hit_stop_timer.start(hit_stop);
+        }
+    }
+
+    fn step_round_reset(&mut self) {
+        self.round_reset_frame += 1;
+        if self.round_reset_frame == ROUND_BLACKOUT_FRAMES {
+            self.reset();
+        }
+        if self.round_reset_frame == ROUND_RESET_FRAMES {
+            self.round_reset_frame = 0;
+        }
+    }
+
+    //How far the blinds are across the screen, from 0 fully open to ROUND_BLACKOUT_FRAMES fully closed
+    pub fn blackout_amount(&self) -> usize {
+        if self.round_reset_frame <= ROUND_BLACKOUT_FRAMES {
+            self.round_reset_frame
+        } else {
+            ROUND_RESET_FRAMES - self.round_reset_frame
+        }
+    }
+
+    fn update_screen_sides(&mut self) {
+        let x_1 = self.players[0].position.x;
+        let x_2 = self.players[1].position.x;
+        if x_1 < x_2 {
+            self.players[0].screen_side = ScreenSideEnum::Left;
+            self.players[1].screen_side = ScreenSideEnum::Right;
+        } else if x_1 > x_2 {
+            self.players[0].screen_side = ScreenSideEnum::Right;
+            self.players[1].screen_side = ScreenSideEnum::Left;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn load_character() -> (ColliderSetComponent, MoveTable) {
+        let hitboxes = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/hitboxes");
+        (
+            ColliderSetComponent::from_file(&hitboxes.join("character_1.json")),
+            MoveTable::from_file(&hitboxes.join("character_1_moves.json")),
+        )
+    }
+
+    //Mashing that is different for each player and every few frames, but the same on every run
+    fn scripted_input(frame: u32, player: u32) -> InputEvents {
+        let mut bits = (frame / 4).wrapping_mul(2654435761) ^ (player + 1).wrapping_mul(40503);
+        bits ^= bits >> 13;
+        bits = bits.wrapping_mul(0x5bd1e995);
+        bits ^= bits >> 15;
+        let button = (bits >> 4) % 8;
+        InputEvents {
+            left_right_axis: (bits % 3) as i8 - 1,
+            up_down_axis: ((bits >> 2) % 3) as i8 - 1,
+            jump_was_pressed: button == 1,
+            light_attack_was_pressed: button == 2,
+            medium_attack_was_pressed: button == 3,
+            heavy_attack_was_pressed: button == 4,
+            special_ability: button == 5,
+            dash: button == 6,
+        }
+    }
+
+    fn scripted_inputs(frame: u32) -> [InputEvents; 2] {
+        [scripted_input(frame, 0), scripted_input(frame, 1)]
+    }
+
+    //The second sim mispredicts a few frames ahead every frame, then rolls back to where it was and
+    //resimulates with the real inputs, the same as a GGRS session would
+    #[test]
+    fn same_inputs_give_the_same_checksums_through_rollbacks_and_a_round_reset() {
+        let (collider_boxes, move_table) = load_character();
+        let mut fight_sim = FightSim::new();
+        let mut rolled_back_sim = FightSim::new();
+        let mut rounds_reset = 0;
+        let mut frame = 0;
+        //Keeps going into the second round for a while, or gives up long after the round timer ran out
+        while rounds_reset < 1 || fight_sim.round_reset_frame > 0 || frame % 600 != 0 {
+            frame += 1;
+            assert!(frame < 20000, "the round never ended");
+
+            let saved = rolled_back_sim.clone();
+            for predicted_frame in frame..frame + 3 {
+                rolled_back_sim.step(
+                    &collider_boxes,
+                    &move_table,
+                    scripted_inputs(predicted_frame + 7919),
+                );
+            }
+            rolled_back_sim = saved;
+
+            fight_sim.step(&collider_boxes, &move_table, scripted_inputs(frame));
+            rolled_back_sim.step(&collider_boxes, &move_table, scripted_inputs(frame));
+            assert_eq!(
+                fight_sim.checksum(),
+                rolled_back_sim.checksum(),
+                "checksums differ on frame {}",
+                frame
+            );
+            if fight_sim.round_reset_frame == ROUND_BLACKOUT_FRAMES {
+                rounds_reset += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn different_inputs_give_different_checksums() {
+        let (collider_boxes, move_table) = load_character();
+        let mut fight_sim = FightSim::new();
+        let mut other_fight_sim = FightSim::new();
+        let mut diverged = false;
+        for frame in 1..120 {
+            fight_sim.step(&collider_boxes, &move_table, scripted_inputs(frame));
+            other_fight_sim.step(&collider_boxes, &move_table, scripted_inputs(frame + 7919));
+            diverged |= fight_sim.checksum() != other_fight_sim.checksum();
+        }
+        assert!(diverged);
+    }
+}
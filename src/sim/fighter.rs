@@ -0,0 +1,113 @@
+use crate::sim::*;
+
+//In subpixels, see SUBPIXELS_PER_PIXEL
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32) -> Position {
+        Position { x, y }
+    }
+}
+
+//Everything the simulation knows about one of the two players
+#[derive(Copy, Clone, Debug, Default, Hash)]
+pub struct Fighter {
+    pub player_state: PlayerState,
+    pub position: Position,
+    pub health: PlayerHealth,
+    pub meter: PlayerMeter,
+    pub screen_side: ScreenSideEnum,
+    pub input_history: InputHistory,
+    //Set on the defender of a strike, so they are drawn shaking while the hit stop lasts
+    pub is_shaking: bool,
+    pub combo: Combo,
+}
+
+impl Fighter {
+    pub fn new(player_id: usize, position: Position, screen_side: ScreenSideEnum) -> Fighter {
+        Fighter {
+            player_state: PlayerState::new(player_id, PlayerStateEnum::Idle),
+            position,
+            health: PlayerHealth::new(),
+            meter: PlayerMeter::new(),
+            screen_side,
+            input_history: InputHistory::default(),
+            is_shaking: false,
+            combo: Combo::default(),
+        }
+    }
+
+    pub fn hard_reset(&mut self, position: Position, screen_side: ScreenSideEnum) {
+        self.player_state.hard_reset();
+        self.health.reset();
+        self.meter.reset();
+        self.position = position;
+        self.screen_side = screen_side;
+        self.input_history.clear();
+        self.is_shaking = false;
+        self.combo.reset();
+    }
+
+    //Nobody moves during hit stop, but presses made in it are still buffered for when it ends
+    pub fn buffer_input(&mut self, input: &InputEvents, move_table: &MoveTable) {
+        self.input_history.push(*input);
+        self.player_state
+            .buffer_pressed_action(&self.input_history, self.screen_side, move_table);
+    }
+
+    pub fn update_state(
+        &mut self,
+        input: &InputEvents,
+        opponent_is_attacking: bool,
+        move_table: &MoveTable,
+    ) {
+        self.input_history.push(*input);
+        self.player_state.tick_timers();
+        self.player_state.available_meter = self.meter.meter;
+        self.player_state.read_input(
+            &self.input_history,
+            self.screen_side,
+            opponent_is_attacking,
+            move_table,
+        );
+
+        if self.player_state.attempt_to_transition_state() || self.player_state.state_is_dirty {
+            self.player_state
+                .enter_desired_state(input, self.screen_side, &move_table.jump);
+            if let Some(current_move) = move_table.get(self.player_state.player_state) {
+                self.meter.spend(current_move.meter_cost);
+            }
+        }
+
+        self.player_state.state_is_dirty = false;
+        self.end_combo_if_recovered();
+    }
+
+    //Animations run on their own frame counter, so how long a drawing is held never changes what the move does
+    pub fn advance_animation(&mut self, collider_boxes: &ColliderSetComponent) {
+        self.player_state.move_frame += 1;
+        let animation = self.player_state.player_state.to_string();
+        let next = self.player_state.animation_frame + 1;
+
+        //As we start it at 0, we should let the system know "we have finished playing a full animation cycle, who wants next"
+        if next >= collider_boxes.animation_length(&animation) {
+            //Attacks hold their last keyframe through whatever recovery their frame data has left
+            if self.player_state.holds_last_keyframe() == false {
+                let desired_state = self.player_state.animation_finished();
+                self.player_state.reset_state();
+                if desired_state != self.player_state.player_state {
+                    self.player_state
+                        .set_player_state_to_transition(desired_state);
+                }
+            }
+        } else {
+            self.player_state.animation_frame = next;
+        }
+        self.player_state.current_sprite_index =
+            collider_boxes.sprite_index(&animation, self.player_state.animation_frame);
+    }
+}
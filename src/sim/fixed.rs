@@ -0,0 +1,13 @@
+//Gameplay positions are plain integers so every machine lands on exactly the same numbers,
+//each pixel is split into this many units so slow movement still has some precision
+pub const SUBPIXELS_PER_PIXEL: i32 = 256;
+
+//Only for data coming in at load time, nothing that runs per frame should touch floats
+pub fn to_fixed(pixels: f32) -> i32 {
+    (pixels * SUBPIXELS_PER_PIXEL as f32).round() as i32
+}
+
+//Only for drawing, never feed the result back into the simulation
+pub fn from_fixed(units: i32) -> f32 {
+    units as f32 / SUBPIXELS_PER_PIXEL as f32
+}
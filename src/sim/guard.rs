@@ -0,0 +1,170 @@
+use crate::sim::*;
+use serde::Deserialize;
+
+//A blocked strike only does this fraction of its damage
+const CHIP_DAMAGE_DIVISOR: usize = 4;
+
+//Where a strike lands, which decides the guard that stops it
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum AttackHeight {
+    //Blocked standing or crouching, the same as Mid
+    High,
+    //Blocked standing or crouching
+    Mid,
+    //Has to be blocked crouching
+    Low,
+    //Has to be blocked standing
+    Overhead,
+}
+
+//What happened to a strike that reached the defender
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StrikeOutcome {
+    Hit,
+    Blocked,
+}
+
+impl AttackHeight {
+    pub fn is_blocked_by(&self, crouching_guard: bool) -> bool {
+        match self {
+            AttackHeight::High | AttackHeight::Mid => true,
+            AttackHeight::Overhead => crouching_guard == false,
+            AttackHeight::Low => crouching_guard,
+        }
+    }
+}
+
+impl PlayerStateEnum {
+    //Every state that performs an attack, each of them needs its frame data in the move table
+    pub const ATTACKS: [PlayerStateEnum; 12] = [
+        PlayerStateEnum::LightAttack,
+        PlayerStateEnum::MediumAttack,
+        PlayerStateEnum::HeavyAttack,
+        PlayerStateEnum::CrouchLightAttack,
+        PlayerStateEnum::CrouchMediumAttack,
+        PlayerStateEnum::CrouchHeavyAttack,
+        PlayerStateEnum::SpecialAbility,
+        PlayerStateEnum::ExSpecialAbility,
+        PlayerStateEnum::Super,
+        PlayerStateEnum::JumpLightAttack,
+        PlayerStateEnum::JumpMediumAttack,
+        PlayerStateEnum::JumpHeavyAttack,
+    ];
+}
+
+impl PlayerState {
+    pub fn is_attacking(&self) -> bool {
+        PlayerStateEnum::ATTACKS.contains(&self.player_state)
+    }
+
+    pub fn is_jump_attacking(&self) -> bool {
+        self.player_state == PlayerStateEnum::JumpLightAttack
+            || self.player_state == PlayerStateEnum::JumpMediumAttack
+            || self.player_state == PlayerStateEnum::JumpHeavyAttack
+    }
+
+    pub fn is_crouching(&self) -> bool {
+        self.player_state == PlayerStateEnum::Crouch
+            || self.player_state == PlayerStateEnum::CrouchBlock
+            || self.player_state == PlayerStateEnum::CrouchLightAttack
+            || self.player_state == PlayerStateEnum::CrouchMediumAttack
+            || self.player_state == PlayerStateEnum::CrouchHeavyAttack
+    }
+
+    //Only a fighter standing on the ground with nothing else to do can put their guard up
+    pub fn can_block(&self) -> bool {
+        self.player_state == PlayerStateEnum::Idle
+            || self.player_state == PlayerStateEnum::Run
+            || self.player_state == PlayerStateEnum::Block
+            || self.player_state == PlayerStateEnum::BlockStun
+            || self.player_state == PlayerStateEnum::Crouch
+            || self.player_state == PlayerStateEnum::CrouchBlock
+    }
+
+    //Swap the hit state a strike would have caused for the blockstun it asks for
+    pub fn block_strike(&mut self, attack: &MoveDefinition) {
+        self.block_stun_timer = AbilityTimer::new(attack.blockstun);
+        self.pushback_speed = attack.block_pushback_speed();
+        self.set_player_state_to_transition(PlayerStateEnum::BlockStun);
+    }
+
+    //Anyone launched off the ground is juggled until they land, anyone hit in the air is knocked up a little first
+    pub fn take_hit(&mut self, attack: &MoveDefinition, airborne: bool) {
+        self.hit_stun_timer = AbilityTimer::new(attack.hitstun);
+        self.pushback_speed = attack.pushback_speed();
+        //Once a juggle is going to end in a knockdown, lighter hits later in it do not change that
+        if attack.knockdown > 0 {
+            self.knockdown_frames = attack.knockdown;
+        } else if self.is_juggled() == false {
+            self.knockdown_frames = 0;
+        }
+        if attack.launch > 0 {
+            self.launch_speed = attack.launch_speed();
+            self.set_player_state_to_transition(PlayerStateEnum::Juggle);
+        } else if airborne {
+            self.launch_speed = JUGGLE_POP_SPEED;
+            self.set_player_state_to_transition(PlayerStateEnum::AirHit);
+        } else if attack.knockdown > 0 {
+            self.set_player_state_to_transition(PlayerStateEnum::Knockdown);
+        } else {
+            self.set_player_state_to_transition(attack.hit_state);
+        }
+    }
+}
+
+impl Fighter {
+    pub fn is_holding_back(&self) -> bool {
+        self.input_history.held(InputButton::back(self.screen_side))
+    }
+
+    pub fn is_holding_down(&self) -> bool {
+        self.input_history.held(InputButton::Down)
+    }
+
+    //Holding away from the attacker with the right guard for the height blocks the strike,
+    //costing a little chip damage instead of the full hit. Returns None if the strike did not connect at all
+    pub fn take_strike(&mut self, attack: &MoveDefinition) -> Option<StrikeOutcome> {
+        //Someone who has been juggled too much just falls through any more hits
+        let airborne = self.player_state.is_juggled() || self.is_airborne();
+        if airborne && self.combo.can_juggle() == false {
+            return None;
+        }
+        self.meter.gain(attack.meter_to_defender);
+        self.is_shaking = true;
+        if self.player_state.can_block()
+            && self.is_holding_back()
+            && attack.height.is_blocked_by(self.is_holding_down())
+        {
+            self.health.take_damage(attack.damage / CHIP_DAMAGE_DIVISOR);
+            self.player_state.block_strike(attack);
+            return Some(StrikeOutcome::Blocked);
+        }
+        let damage = self.combo.scale_damage(attack.damage);
+        self.health.take_damage(damage);
+        self.combo.hits += 1;
+        if airborne || attack.launch > 0 {
+            self.combo.juggle_points += attack.juggle_cost;
+        }
+        self.player_state.take_hit(attack, airborne);
+        Some(StrikeOutcome::Hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_height_is_blocked_by_its_guards() {
+        let standing = false;
+        let crouching = true;
+        assert!(AttackHeight::High.is_blocked_by(standing));
+        assert!(AttackHeight::High.is_blocked_by(crouching));
+        assert!(AttackHeight::Mid.is_blocked_by(standing));
+        assert!(AttackHeight::Mid.is_blocked_by(crouching));
+        assert!(AttackHeight::Low.is_blocked_by(standing) == false);
+        assert!(AttackHeight::Low.is_blocked_by(crouching));
+        assert!(AttackHeight::Overhead.is_blocked_by(standing));
+        assert!(AttackHeight::Overhead.is_blocked_by(crouching) == false);
+    }
+}
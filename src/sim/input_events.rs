@@ -0,0 +1,218 @@
+use bevy::prelude::*;
+use ggrs::GameInput;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+//Both axes and the version share the first byte, the buttons get the second
+pub const INPUT_SIZE: usize = 2;
+//Bump whenever the wire format changes, so mismatched builds refuse to play instead of misreading inputs
+//Never 0, an all zero buffer is what GGRS hands us before the other side has sent anything
+pub const INPUT_VERSION: u8 = 1;
+
+const LEFT_RIGHT_SHIFT: u8 = 0;
+const UP_DOWN_SHIFT: u8 = 2;
+const VERSION_SHIFT: u8 = 4;
+const AXIS_MASK: u8 = 0b11;
+
+const JUMP_BIT: u8 = 1 << 0;
+const HEAVY_ATTACK_BIT: u8 = 1 << 1;
+const MEDIUM_ATTACK_BIT: u8 = 1 << 2;
+const LIGHT_ATTACK_BIT: u8 = 1 << 3;
+const SPECIAL_ABILITY_BIT: u8 = 1 << 4;
+const DASH_BIT: u8 = 1 << 5;
+
+//What is wrong with an input from the other player, it means they are running a different build
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InputFormatError {
+    WrongSize { received: usize, expected: usize },
+    WrongVersion { received: u8, expected: u8 },
+}
+
+impl fmt::Display for InputFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputFormatError::WrongSize { received, expected } => write!(
+                f,
+                "received a {} byte input, this build expects {} bytes, both players need the same build",
+                received, expected
+            ),
+            InputFormatError::WrongVersion { received, expected } => write!(
+                f,
+                "received input format version {}, this build speaks version {}, both players need the same build",
+                received, expected
+            ),
+        }
+    }
+}
+
+impl Error for InputFormatError {}
+
+//A Distilation of the true input state
+#[derive(Default, Copy, Clone, Debug, PartialEq, Reflect, Hash, Serialize, Deserialize)]
+pub struct InputEvents {
+    pub left_right_axis: i8,
+    pub up_down_axis: i8,
+    pub jump_was_pressed: bool,
+    pub heavy_attack_was_pressed: bool,
+    pub light_attack_was_pressed: bool,
+    pub medium_attack_was_pressed: bool,
+    pub special_ability: bool,
+    pub dash: bool,
+}
+
+impl InputEvents {
+    pub fn encode(&self) -> [u8; INPUT_SIZE] {
+        let axes = encode_axis(self.left_right_axis) << LEFT_RIGHT_SHIFT
+            | encode_axis(self.up_down_axis) << UP_DOWN_SHIFT
+            | INPUT_VERSION << VERSION_SHIFT;
+        let mut buttons = 0;
+        for (held, bit) in [
+            (self.jump_was_pressed, JUMP_BIT),
+            (self.heavy_attack_was_pressed, HEAVY_ATTACK_BIT),
+            (self.medium_attack_was_pressed, MEDIUM_ATTACK_BIT),
+            (self.light_attack_was_pressed, LIGHT_ATTACK_BIT),
+            (self.special_ability, SPECIAL_ABILITY_BIT),
+            (self.dash, DASH_BIT),
+        ] {
+            if held {
+                buttons |= bit;
+            }
+        }
+        [axes, buttons]
+    }
+
+    //bevy_ggrs wants the input handed over as a Vec
+    pub fn convert_input_events_into_vector(&self) -> Vec<u8> {
+        self.encode().to_vec()
+    }
+
+    pub fn decode(buffer: &[u8]) -> Result<InputEvents, InputFormatError> {
+        if buffer.len() != INPUT_SIZE {
+            return Err(InputFormatError::WrongSize {
+                received: buffer.len(),
+                expected: INPUT_SIZE,
+            });
+        }
+        if buffer.iter().all(|byte| *byte == 0) {
+            return Ok(InputEvents::default());
+        }
+        let version = buffer[0] >> VERSION_SHIFT;
+        if version != INPUT_VERSION {
+            return Err(InputFormatError::WrongVersion {
+                received: version,
+                expected: INPUT_VERSION,
+            });
+        }
+        let buttons = buffer[1];
+        Ok(InputEvents {
+            left_right_axis: decode_axis(buffer[0] >> LEFT_RIGHT_SHIFT),
+            up_down_axis: decode_axis(buffer[0] >> UP_DOWN_SHIFT),
+            jump_was_pressed: buttons & JUMP_BIT != 0,
+            heavy_attack_was_pressed: buttons & HEAVY_ATTACK_BIT != 0,
+            medium_attack_was_pressed: buttons & MEDIUM_ATTACK_BIT != 0,
+            light_attack_was_pressed: buttons & LIGHT_ATTACK_BIT != 0,
+            special_ability: buttons & SPECIAL_ABILITY_BIT != 0,
+            dash: buttons & DASH_BIT != 0,
+        })
+    }
+
+    pub fn from_input_vector(
+        input: &[GameInput],
+        player_index: usize,
+    ) -> Result<InputEvents, InputFormatError> {
+        let game_input = &input[player_index];
+        InputEvents::decode(&game_input.buffer[..game_input.size])
+    }
+}
+
+//0 is neutral, 1 is left or up, 2 is right or down
+fn encode_axis(axis: i8) -> u8 {
+    match axis {
+        -1 => 1,
+        1 => 2,
+        _ => 0,
+    }
+}
+
+fn decode_axis(bits: u8) -> i8 {
+    match bits & AXIS_MASK {
+        1 => -1,
+        2 => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //One InputEvents for every combination of the six buttons, picked by the bits of mask
+    fn with_buttons(left_right_axis: i8, up_down_axis: i8, mask: u8) -> InputEvents {
+        InputEvents {
+            left_right_axis,
+            up_down_axis,
+            jump_was_pressed: mask & (1 << 0) != 0,
+            heavy_attack_was_pressed: mask & (1 << 1) != 0,
+            light_attack_was_pressed: mask & (1 << 2) != 0,
+            medium_attack_was_pressed: mask & (1 << 3) != 0,
+            special_ability: mask & (1 << 4) != 0,
+            dash: mask & (1 << 5) != 0,
+        }
+    }
+
+    #[test]
+    fn every_input_survives_the_round_trip() {
+        for left_right_axis in [-1, 0, 1] {
+            for up_down_axis in [-1, 0, 1] {
+                for mask in 0..(1 << 6) {
+                    let input = with_buttons(left_right_axis, up_down_axis, mask);
+                    let encoded = input.encode();
+                    assert_eq!(encoded.len(), INPUT_SIZE);
+                    assert_eq!(InputEvents::decode(&encoded), Ok(input));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_sent_yet_decodes_as_no_input() {
+        assert_eq!(
+            InputEvents::decode(&[0; INPUT_SIZE]),
+            Ok(InputEvents::default())
+        );
+    }
+
+    #[test]
+    fn wrong_length_is_an_error() {
+        let encoded = InputEvents::default().encode();
+        assert_eq!(
+            InputEvents::decode(&encoded[..1]),
+            Err(InputFormatError::WrongSize {
+                received: 1,
+                expected: INPUT_SIZE
+            })
+        );
+        assert_eq!(
+            InputEvents::decode(&[encoded[0], encoded[1], 0]),
+            Err(InputFormatError::WrongSize {
+                received: 3,
+                expected: INPUT_SIZE
+            })
+        );
+    }
+
+    #[test]
+    fn wrong_version_is_an_error() {
+        let mut encoded = with_buttons(1, -1, 0b101).encode();
+        encoded[0] =
+            (encoded[0] & !(0b1111 << VERSION_SHIFT)) | (INPUT_VERSION + 1) << VERSION_SHIFT;
+        assert_eq!(
+            InputEvents::decode(&encoded),
+            Err(InputFormatError::WrongVersion {
+                received: INPUT_VERSION + 1,
+                expected: INPUT_VERSION
+            })
+        );
+    }
+}
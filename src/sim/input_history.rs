@@ -0,0 +1,205 @@
+use crate::sim::*;
+
+//Long enough for the slowest motion input we read, a full charge plus the time to release it
+pub const INPUT_HISTORY_LENGTH: usize = 64;
+
+//Every button and direction a fighter can be asked about, directions are as the player sees the screen
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InputButton {
+    Left,
+    Right,
+    //Nothing reads up on its own yet, jumping has its own button
+    #[allow(dead_code)]
+    Up,
+    Down,
+    Jump,
+    LightAttack,
+    MediumAttack,
+    HeavyAttack,
+    SpecialAbility,
+    Dash,
+}
+
+impl InputButton {
+    //Whichever of Left or Right points away from the other fighter
+    pub fn back(screen_side: ScreenSideEnum) -> InputButton {
+        match screen_side {
+            ScreenSideEnum::Left => InputButton::Left,
+            ScreenSideEnum::Right => InputButton::Right,
+        }
+    }
+
+    pub fn held_in(&self, input: &InputEvents) -> bool {
+        match self {
+            InputButton::Left => input.left_right_axis < 0,
+            InputButton::Right => input.left_right_axis > 0,
+            InputButton::Up => input.up_down_axis < 0,
+            InputButton::Down => input.up_down_axis > 0,
+            InputButton::Jump => input.jump_was_pressed,
+            InputButton::LightAttack => input.light_attack_was_pressed,
+            InputButton::MediumAttack => input.medium_attack_was_pressed,
+            InputButton::HeavyAttack => input.heavy_attack_was_pressed,
+            InputButton::SpecialAbility => input.special_ability,
+            InputButton::Dash => input.dash,
+        }
+    }
+}
+
+//The last INPUT_HISTORY_LENGTH inputs one fighter has seen, hit stop included, newest first.
+//Lives inside of FightSim, so it is saved and restored with every rollback like everything else
+#[derive(Copy, Clone, Debug, Hash)]
+pub struct InputHistory {
+    frames: [InputEvents; INPUT_HISTORY_LENGTH],
+    newest: usize,
+    length: usize,
+}
+
+//Arrays this long have no Default of their own
+impl Default for InputHistory {
+    fn default() -> InputHistory {
+        InputHistory {
+            frames: [InputEvents::default(); INPUT_HISTORY_LENGTH],
+            newest: 0,
+            length: 0,
+        }
+    }
+}
+
+impl InputHistory {
+    pub fn push(&mut self, input: InputEvents) {
+        self.newest = (self.newest + 1) % INPUT_HISTORY_LENGTH;
+        self.frames[self.newest] = input;
+        self.length = (self.length + 1).min(INPUT_HISTORY_LENGTH);
+    }
+
+    pub fn clear(&mut self) {
+        *self = InputHistory::default();
+    }
+
+    //0 is the input for this frame, 1 the one before it and so on, None once it is older than we keep
+    pub fn frames_ago(&self, frames_ago: usize) -> Option<&InputEvents> {
+        if frames_ago >= self.length {
+            return None;
+        }
+        let index = (self.newest + INPUT_HISTORY_LENGTH - frames_ago) % INPUT_HISTORY_LENGTH;
+        Some(&self.frames[index])
+    }
+
+    pub fn current(&self) -> InputEvents {
+        self.frames_ago(0).cloned().unwrap_or_default()
+    }
+
+    pub fn held(&self, button: InputButton) -> bool {
+        button.held_in(&self.current())
+    }
+
+    //Anything from before the history started counts as not held, so a button held going into a round presses on its first frame
+    fn was_held(&self, button: InputButton, frames_ago: usize) -> bool {
+        self.frames_ago(frames_ago)
+            .map(|input| button.held_in(input))
+            .unwrap_or(false)
+    }
+
+    pub fn pressed_this_frame(&self, button: InputButton) -> bool {
+        self.was_held(button, 0) && !self.was_held(button, 1)
+    }
+
+    //The rest of the query API the history offers, nothing in the state machine reads releases or hold lengths yet
+    #[allow(dead_code)]
+    pub fn released_this_frame(&self, button: InputButton) -> bool {
+        !self.was_held(button, 0) && self.was_held(button, 1)
+    }
+
+    //True once the button has been down for at least this many frames in a row, this frame included
+    #[allow(dead_code)]
+    pub fn held_for(&self, button: InputButton, frames: usize) -> bool {
+        frames > 0 && (0..frames).all(|frames_ago| self.was_held(button, frames_ago))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_jump(jump_was_pressed: bool) -> InputEvents {
+        InputEvents {
+            jump_was_pressed,
+            ..InputEvents::default()
+        }
+    }
+
+    //Fills the ring past its end so every check below reads across the wrap
+    fn wrapped_history(newest_frames: &[bool]) -> InputHistory {
+        let mut history = InputHistory::default();
+        for _ in 0..INPUT_HISTORY_LENGTH + 3 {
+            history.push(with_jump(false));
+        }
+        for held in newest_frames {
+            history.push(with_jump(*held));
+        }
+        history
+    }
+
+    #[test]
+    fn press_is_only_seen_on_its_first_frame() {
+        let history = wrapped_history(&[true]);
+        assert!(history.pressed_this_frame(InputButton::Jump));
+        let history = wrapped_history(&[true, true]);
+        assert!(history.pressed_this_frame(InputButton::Jump) == false);
+        assert!(history.held(InputButton::Jump));
+    }
+
+    #[test]
+    fn release_is_only_seen_on_its_first_frame() {
+        let history = wrapped_history(&[true, false]);
+        assert!(history.released_this_frame(InputButton::Jump));
+        let history = wrapped_history(&[true, false, false]);
+        assert!(history.released_this_frame(InputButton::Jump) == false);
+        let history = wrapped_history(&[false]);
+        assert!(history.released_this_frame(InputButton::Jump) == false);
+    }
+
+    #[test]
+    fn held_for_counts_frames_in_a_row() {
+        let history = wrapped_history(&[true, true, true]);
+        assert!(history.held_for(InputButton::Jump, 3));
+        assert!(history.held_for(InputButton::Jump, 4) == false);
+        assert!(history.held_for(InputButton::Jump, 0) == false);
+        let history = wrapped_history(&[true, false, true, true]);
+        assert!(history.held_for(InputButton::Jump, 2));
+        assert!(history.held_for(InputButton::Jump, 3) == false);
+    }
+
+    #[test]
+    fn held_for_the_whole_history_reads_every_slot_of_the_ring() {
+        let held = [true; INPUT_HISTORY_LENGTH];
+        let history = wrapped_history(&held);
+        assert!(history.held_for(InputButton::Jump, INPUT_HISTORY_LENGTH));
+        //Nothing older than the history is kept, so it can never have been held for longer
+        assert!(history.held_for(InputButton::Jump, INPUT_HISTORY_LENGTH + 1) == false);
+    }
+
+    #[test]
+    fn a_button_held_into_an_empty_history_presses_on_its_first_frame() {
+        let mut history = InputHistory::default();
+        history.push(with_jump(true));
+        assert!(history.pressed_this_frame(InputButton::Jump));
+        assert!(history.released_this_frame(InputButton::Jump) == false);
+    }
+
+    #[test]
+    fn directions_follow_the_axes() {
+        let mut history = InputHistory::default();
+        history.push(InputEvents {
+            left_right_axis: -1,
+            up_down_axis: 1,
+            ..InputEvents::default()
+        });
+        assert!(history.held(InputButton::Left));
+        assert!(history.held(InputButton::Right) == false);
+        assert!(history.held(InputButton::Down));
+        assert!(history.held(InputButton::Up) == false);
+        assert_eq!(InputButton::back(ScreenSideEnum::Left), InputButton::Left);
+        assert_eq!(InputButton::back(ScreenSideEnum::Right), InputButton::Right);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::sim::*;
+use serde::Deserialize;
+
+//How a character jumps, every duration is in frames and every speed is in whole pixels per frame,
+//so an arc always lands on the same subpixels on both sides of an online match
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JumpData {
+    //Crouching down before leaving the ground, the direction held as it ends picks the arc
+    pub prejump: usize,
+    pub speed: i32,
+    pub forward_speed: i32,
+    pub back_speed: i32,
+    //How many more times we can jump before landing, 0 for no double jump
+    pub air_jumps: usize,
+    //Lets us dash once per jump while in the air
+    pub air_dash: bool,
+    //How long we are stuck on the ground after landing from a jump
+    pub landing_recovery: usize,
+}
+
+impl JumpData {
+    //Straight up unless forward or back is held, directions are as the player sees the screen
+    pub fn x_speed(&self, left_right_axis: i32, screen_side: ScreenSideEnum) -> i32 {
+        if left_right_axis == 0 {
+            return 0;
+        }
+        let speed = if left_right_axis == screen_side.back_direction() {
+            self.back_speed
+        } else {
+            self.forward_speed
+        };
+        left_right_axis * speed * SUBPIXELS_PER_PIXEL
+    }
+
+    pub fn y_speed(&self) -> i32 {
+        self.speed * SUBPIXELS_PER_PIXEL
+    }
+}
+
+impl PlayerState {
+    //Jump and Fall are the only states in the air we can still act out of
+    pub fn is_jumping(&self) -> bool {
+        self.player_state == PlayerStateEnum::Jump || self.player_state == PlayerStateEnum::Fall
+    }
+
+    //Returns true while we are getting off the ground or landing back on it, so nothing else gets read this frame
+    pub fn read_jump(&mut self) -> bool {
+        if self.player_state == PlayerStateEnum::PreJump {
+            if self.jump_timer.running() == false {
+                self.set_player_state_to_transition(PlayerStateEnum::Jump);
+            }
+            return true;
+        }
+
+        if self.player_state == PlayerStateEnum::Landing {
+            if self.jump_timer.running() {
+                return true;
+            }
+            //Whatever was buffered while landing comes out straight away
+            self.set_player_state_to_transition(PlayerStateEnum::Idle);
+        }
+        false
+    }
+}
@@ -0,0 +1,55 @@
+use crate::sim::*;
+use serde::Deserialize;
+
+//How a character gets back up after being knocked down, every duration is in frames
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WakeUpData {
+    pub wake_up: usize,
+    //Holding up as the knockdown ends gets up in this long instead
+    pub quick_rise: usize,
+    //Holding back as the knockdown ends rolls away for this long instead
+    pub back_roll: usize,
+    //How fast the back roll goes, in pixels per frame
+    pub back_roll_speed: i32,
+}
+
+impl PlayerState {
+    //On the ground or getting up from it, nothing can touch us and we have no body to touch
+    pub fn is_knocked_down(&self) -> bool {
+        self.player_state == PlayerStateEnum::Knockdown
+            || self.player_state == PlayerStateEnum::WakeUp
+            || self.player_state == PlayerStateEnum::BackRoll
+    }
+
+    //Returns true while the knockdown is still going, so nothing else gets read this frame
+    pub fn read_wake_up(
+        &mut self,
+        input: &InputEvents,
+        screen_side: ScreenSideEnum,
+        wake_up: &WakeUpData,
+    ) -> bool {
+        if self.is_knocked_down() == false || self.knockdown_timer.running() {
+            return self.is_knocked_down();
+        }
+
+        if self.player_state == PlayerStateEnum::Knockdown {
+            if input.left_right_axis as i32 == screen_side.back_direction() {
+                self.knockdown_timer = AbilityTimer::new(wake_up.back_roll);
+                self.pushback_speed = wake_up.back_roll_speed * SUBPIXELS_PER_PIXEL;
+                self.set_player_state_to_transition(PlayerStateEnum::BackRoll);
+            } else if input.jump_was_pressed {
+                self.knockdown_timer = AbilityTimer::new(wake_up.quick_rise);
+                self.set_player_state_to_transition(PlayerStateEnum::WakeUp);
+            } else {
+                self.knockdown_timer = AbilityTimer::new(wake_up.wake_up);
+                self.set_player_state_to_transition(PlayerStateEnum::WakeUp);
+            }
+            return true;
+        }
+
+        //Back on our feet, whatever was buffered while getting up comes out straight away
+        self.set_player_state_to_transition(PlayerStateEnum::Idle);
+        false
+    }
+}
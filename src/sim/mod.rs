@@ -0,0 +1,43 @@
+mod checksum;
+mod collision;
+mod combo;
+mod fight_sim;
+mod fighter;
+mod fixed;
+mod guard;
+mod input_events;
+mod input_history;
+mod jump;
+mod knockdown;
+mod motion_input;
+mod move_table;
+mod movement;
+mod player_health;
+mod player_meter;
+mod player_state;
+mod projectile;
+mod round_timer;
+mod screen_side;
+mod throw;
+
+pub use self::checksum::*;
+pub use self::collision::*;
+pub use self::combo::*;
+pub use self::fight_sim::*;
+pub use self::fighter::*;
+pub use self::fixed::*;
+pub use self::guard::*;
+pub use self::input_events::*;
+pub use self::input_history::*;
+pub use self::jump::*;
+pub use self::knockdown::*;
+pub use self::motion_input::*;
+pub use self::move_table::*;
+pub use self::movement::*;
+pub use self::player_health::*;
+pub use self::player_meter::*;
+pub use self::player_state::*;
+pub use self::projectile::*;
+pub use self::round_timer::*;
+pub use self::screen_side::*;
+pub use self::throw::*;
@@ -0,0 +1,326 @@
+use crate::sim::*;
+
+//How long back has to be held before a charge motion is ready
+pub const CHARGE_FRAMES: usize = 40;
+
+//Directions in numpad notation, as if the fighter was on the left facing right.
+//5 is neutral, 6 is towards the opponent, 4 is away from them, 2 is down and so on
+pub fn numpad_direction(input: &InputEvents, screen_side: ScreenSideEnum) -> u8 {
+    let forward = -(input.left_right_axis as i32) * screen_side.back_direction();
+    let row = match input.up_down_axis {
+        up_down if up_down > 0 => 0,
+        0 => 3,
+        _ => 6,
+    };
+    let column = match forward {
+        forward if forward < 0 => 1,
+        0 => 2,
+        _ => 3,
+    };
+    row + column
+}
+
+fn is_back(direction: u8) -> bool {
+    direction == 1 || direction == 4 || direction == 7
+}
+
+fn is_forward(direction: u8) -> bool {
+    direction == 3 || direction == 6 || direction == 9
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MotionInput {
+    //236
+    QuarterCircleForward,
+    //214
+    QuarterCircleBack,
+    //623
+    DragonPunch,
+    //41236
+    HalfCircleForward,
+    //66
+    DoubleTapForward,
+    //44
+    DoubleTapBack,
+    //[4]6
+    Charge,
+}
+
+impl MotionInput {
+    //Longest motions first, so a 41236 is never mistaken for the 236 at the end of it, see completed_motion
+    pub const ALL: [MotionInput; 7] = [
+        MotionInput::HalfCircleForward,
+        MotionInput::DragonPunch,
+        MotionInput::QuarterCircleForward,
+        MotionInput::QuarterCircleBack,
+        MotionInput::Charge,
+        MotionInput::DoubleTapForward,
+        MotionInput::DoubleTapBack,
+    ];
+
+    //The directions that make up the motion, in the order they have to be entered
+    fn steps(&self) -> &'static [u8] {
+        match self {
+            MotionInput::QuarterCircleForward => &[2, 3, 6],
+            MotionInput::QuarterCircleBack => &[2, 1, 4],
+            MotionInput::DragonPunch => &[6, 2, 3],
+            MotionInput::HalfCircleForward => &[4, 1, 2, 3, 6],
+            MotionInput::DoubleTapForward => &[6, 5, 6],
+            MotionInput::DoubleTapBack => &[4, 5, 4],
+            MotionInput::Charge => &[],
+        }
+    }
+
+    //How many frames, counting back from this one, the whole motion has to fit in
+    fn leniency(&self) -> usize {
+        match self {
+            MotionInput::QuarterCircleForward => 15,
+            MotionInput::QuarterCircleBack => 15,
+            MotionInput::DragonPunch => 18,
+            MotionInput::HalfCircleForward => 24,
+            MotionInput::DoubleTapForward => 12,
+            MotionInput::DoubleTapBack => 12,
+            //Time allowed between letting go of back and reaching forward
+            MotionInput::Charge => 8,
+        }
+    }
+
+    //Dashes come out of the motion alone, so they only count on the frame the last tap lands
+    fn needs_fresh_last_step(&self) -> bool {
+        match self {
+            MotionInput::DoubleTapForward | MotionInput::DoubleTapBack => true,
+            _ => false,
+        }
+    }
+}
+
+impl InputHistory {
+    fn direction(&self, frames_ago: usize, screen_side: ScreenSideEnum) -> Option<u8> {
+        self.frames_ago(frames_ago)
+            .map(|input| numpad_direction(input, screen_side))
+    }
+
+    //Forward and back are worked out from the side the fighter is on now, so a cross up flips them
+    pub fn completed(&self, motion: MotionInput, screen_side: ScreenSideEnum) -> bool {
+        if motion == MotionInput::Charge {
+            return self.completed_charge(screen_side, motion.leniency());
+        }
+        let steps = motion.steps();
+        let last_step = steps[steps.len() - 1];
+        if motion.needs_fresh_last_step()
+            && (self.direction(0, screen_side) != Some(last_step)
+                || self.direction(1, screen_side) == Some(last_step))
+        {
+            return false;
+        }
+
+        //Walk backwards through time, matching the motion from its last step to its first,
+        //anything in between the steps is ignored so slightly sloppy motions still come out
+        let mut remaining = steps.len();
+        for frames_ago in 0..motion.leniency() {
+            let direction = match self.direction(frames_ago, screen_side) {
+                Some(direction) => direction,
+                None => return false,
+            };
+            if direction == steps[remaining - 1] {
+                remaining -= 1;
+                if remaining == 0 {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    //The one motion a button press finishes, if any, picked in the order of MotionInput::ALL
+    pub fn completed_motion(&self, screen_side: ScreenSideEnum) -> Option<MotionInput> {
+        MotionInput::ALL
+            .iter()
+            .cloned()
+            .find(|motion| self.completed(*motion, screen_side))
+    }
+
+    fn completed_charge(&self, screen_side: ScreenSideEnum, leniency: usize) -> bool {
+        let is_forward_at =
+            |frames_ago| self.direction(frames_ago, screen_side).map(is_forward) == Some(true);
+        let is_back_at =
+            |frames_ago| self.direction(frames_ago, screen_side).map(is_back) == Some(true);
+        if !is_forward_at(0) {
+            return false;
+        }
+
+        let charge_end = match (1..=leniency).find(|frames_ago| is_back_at(*frames_ago)) {
+            Some(charge_end) => charge_end,
+            None => return false,
+        };
+        (charge_end..charge_end + CHARGE_FRAMES).all(is_back_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIDES: [ScreenSideEnum; 2] = [ScreenSideEnum::Left, ScreenSideEnum::Right];
+
+    //The input that points in a numpad direction for a fighter on screen_side
+    fn toward(direction: u8, screen_side: ScreenSideEnum) -> InputEvents {
+        let forward = (direction as i32 - 1) % 3 - 1;
+        let up_down_axis = match (direction - 1) / 3 {
+            0 => 1,
+            1 => 0,
+            _ => -1,
+        };
+        InputEvents {
+            left_right_axis: (-forward * screen_side.back_direction()) as i8,
+            up_down_axis,
+            ..InputEvents::default()
+        }
+    }
+
+    //Oldest direction first, the last one is this frame
+    fn history(directions: &[u8], screen_side: ScreenSideEnum) -> InputHistory {
+        let mut history = InputHistory::default();
+        for direction in directions {
+            history.push(toward(*direction, screen_side));
+        }
+        history
+    }
+
+    //Each motion done as cleanly as it can be
+    fn clean_motion(motion: MotionInput) -> Vec<u8> {
+        match motion {
+            MotionInput::Charge => {
+                let mut directions = vec![4; CHARGE_FRAMES];
+                directions.push(6);
+                directions
+            }
+            _ => motion.steps().to_vec(),
+        }
+    }
+
+    #[test]
+    fn every_motion_is_read_on_both_sides() {
+        for screen_side in SIDES {
+            for motion in MotionInput::ALL {
+                let history = history(&clean_motion(motion), screen_side);
+                assert_eq!(
+                    history.completed_motion(screen_side),
+                    Some(motion),
+                    "{:?} on the {:?}",
+                    motion,
+                    screen_side
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn crossing_over_flips_forward_and_back() {
+        let quarter_circle = history(&[2, 3, 6], ScreenSideEnum::Left);
+        assert_eq!(
+            quarter_circle.completed_motion(ScreenSideEnum::Right),
+            Some(MotionInput::QuarterCircleBack)
+        );
+        let double_tap = history(&[6, 5, 6], ScreenSideEnum::Right);
+        assert_eq!(
+            double_tap.completed_motion(ScreenSideEnum::Left),
+            Some(MotionInput::DoubleTapBack)
+        );
+    }
+
+    #[test]
+    fn motions_have_to_fit_in_their_leniency() {
+        for screen_side in SIDES {
+            for motion in MotionInput::ALL {
+                if motion == MotionInput::Charge {
+                    continue;
+                }
+                //The first step as long ago as the leniency allows, then one frame too early
+                let steps = motion.steps();
+                for (padding, completes) in [
+                    (motion.leniency() - steps.len(), true),
+                    (motion.leniency() - steps.len() + 1, false),
+                ] {
+                    let mut directions = vec![steps[0]];
+                    directions.extend(vec![5; padding]);
+                    directions.extend_from_slice(&steps[1..]);
+                    assert_eq!(
+                        history(&directions, screen_side).completed(motion, screen_side),
+                        completes,
+                        "{:?} on the {:?} spread over {} frames",
+                        motion,
+                        screen_side,
+                        directions.len()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn half_circle_is_not_read_as_the_quarter_circle_at_its_end() {
+        let half_circle = history(&[4, 1, 2, 3, 6], ScreenSideEnum::Left);
+        assert!(half_circle.completed(MotionInput::QuarterCircleForward, ScreenSideEnum::Left));
+        assert_eq!(
+            half_circle.completed_motion(ScreenSideEnum::Left),
+            Some(MotionInput::HalfCircleForward)
+        );
+        let quarter_circle = history(&[5, 2, 3, 6], ScreenSideEnum::Left);
+        assert_eq!(
+            quarter_circle.completed_motion(ScreenSideEnum::Left),
+            Some(MotionInput::QuarterCircleForward)
+        );
+    }
+
+    #[test]
+    fn charge_has_to_be_held_for_charge_frames() {
+        for screen_side in SIDES {
+            let mut too_short = vec![5];
+            too_short.extend(vec![4; CHARGE_FRAMES - 1]);
+            too_short.push(6);
+            assert!(
+                history(&too_short, screen_side).completed(MotionInput::Charge, screen_side)
+                    == false
+            );
+
+            let mut long_enough = vec![5];
+            long_enough.extend(vec![4; CHARGE_FRAMES]);
+            long_enough.push(6);
+            assert!(history(&long_enough, screen_side).completed(MotionInput::Charge, screen_side));
+        }
+    }
+
+    //Special pressed on the last of the directions, heavy held through all of them if asked for
+    fn special_after(directions: &[u8], holding_heavy: bool) -> Option<PlayerStateEnum> {
+        let mut history = InputHistory::default();
+        for (frame, direction) in directions.iter().enumerate() {
+            history.push(InputEvents {
+                special_ability: frame == directions.len() - 1,
+                heavy_attack_was_pressed: holding_heavy,
+                ..toward(*direction, ScreenSideEnum::Left)
+            });
+        }
+        let mut player_state = PlayerState::new(0, PlayerStateEnum::Idle);
+        player_state.buffer_pressed_action(&history, ScreenSideEnum::Left, &MoveTable::default());
+        player_state.input_buffer.action()
+    }
+
+    #[test]
+    fn special_comes_out_of_its_motions() {
+        assert_eq!(special_after(&[5, 5], false), None);
+        assert_eq!(special_after(&[5, 5], true), None);
+        assert_eq!(
+            special_after(&[2, 3, 6], false),
+            Some(PlayerStateEnum::SpecialAbility)
+        );
+        assert_eq!(
+            special_after(&[2, 3, 6], true),
+            Some(PlayerStateEnum::ExSpecialAbility)
+        );
+        assert_eq!(
+            special_after(&[4, 1, 2, 3, 6], false),
+            Some(PlayerStateEnum::Super)
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::sim::*;
+use serde::Deserialize;
+
+//Everything about one attack that designers tune, every duration is in frames
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MoveDefinition {
+    pub startup: usize,
+    pub active: usize,
+    pub recovery: usize,
+    pub damage: usize,
+    pub hitstun: usize,
+    pub blockstun: usize,
+    //How fast the defender slides away, in pixels per frame
+    pub pushback: i32,
+    pub block_pushback: i32,
+    pub hitstop: usize,
+    pub height: AttackHeight,
+    //How many juggle points it uses up when it hits someone in the air
+    pub juggle_cost: usize,
+    //How fast it knocks a grounded defender up into a juggle, in pixels per frame, 0 leaves them standing
+    #[serde(default)]
+    pub launch: i32,
+    //Which of the TakeHit states the defender is put into
+    pub hit_state: PlayerStateEnum,
+    //The frames of the move, first and last included, that it can be cancelled on
+    #[serde(default)]
+    pub cancel_window: Option<(usize, usize)>,
+    //Every state this move can be cancelled into, once it has hit or been blocked
+    #[serde(default)]
+    pub cancels_into: Vec<PlayerStateEnum>,
+    //Anything the move throws, which then strikes with the rest of this frame data instead of the fighter
+    #[serde(default)]
+    pub projectile: Option<ProjectileDefinition>,
+    //Meter the attacker gains when the move hits or is blocked, and the defender gains either way
+    #[serde(default)]
+    pub meter_on_hit: usize,
+    #[serde(default)]
+    pub meter_on_block: usize,
+    #[serde(default)]
+    pub meter_to_defender: usize,
+    //Meter it takes to do the move at all
+    #[serde(default)]
+    pub meter_cost: usize,
+    //Freezes everyone for this long on the first frame of the move, so the opponent gets to see it coming
+    #[serde(default)]
+    pub super_freeze: usize,
+    //How long a defender it hits lies on the ground for, once they land if it juggled them, 0 never knocks down
+    #[serde(default)]
+    pub knockdown: usize,
+}
+
+impl MoveDefinition {
+    pub fn total_frames(&self) -> usize {
+        self.startup + self.active + self.recovery
+    }
+
+    pub fn is_active(&self, move_frame: usize) -> bool {
+        move_frame >= self.startup && move_frame < self.startup + self.active
+    }
+
+    pub fn can_cancel(&self, move_frame: usize) -> bool {
+        match self.cancel_window {
+            Some((first, last)) => move_frame >= first && move_frame <= last,
+            None => false,
+        }
+    }
+
+    //Only a move that connected can be cancelled, and only into one of its routes inside its window
+    pub fn can_cancel_into(
+        &self,
+        next_state: PlayerStateEnum,
+        move_frame: usize,
+        move_connected: bool,
+    ) -> bool {
+        move_connected && self.can_cancel(move_frame) && self.cancels_into.contains(&next_state)
+    }
+
+    pub fn attacker_meter(&self, outcome: StrikeOutcome) -> usize {
+        match outcome {
+            StrikeOutcome::Hit => self.meter_on_hit,
+            StrikeOutcome::Blocked => self.meter_on_block,
+        }
+    }
+
+    pub fn pushback_speed(&self) -> i32 {
+        self.pushback * SUBPIXELS_PER_PIXEL
+    }
+
+    pub fn block_pushback_speed(&self) -> i32 {
+        self.block_pushback * SUBPIXELS_PER_PIXEL
+    }
+
+    pub fn launch_speed(&self) -> i32 {
+        self.launch * SUBPIXELS_PER_PIXEL
+    }
+}
+
+//Every attack one character has, keyed by the state that performs it,
+//how they get back up from the ones that knock them down, how they jump, and how long their presses are buffered
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MoveTable {
+    pub moves: HashMap<PlayerStateEnum, MoveDefinition>,
+    pub wake_up: WakeUpData,
+    pub jump: JumpData,
+    //How many frames a press is remembered for while the fighter is still busy
+    pub input_buffer: usize,
+}
+
+impl MoveTable {
+    pub fn from_file(path: &Path) -> MoveTable {
+        let file_contents = fs::read_to_string(path).unwrap();
+        MoveTable::from_json(&file_contents)
+    }
+
+    //Every attack has to have its frame data, and can only put the defender into one of the TakeHit states
+    pub fn from_json(json: &str) -> MoveTable {
+        let deserialized: MoveTable = serde_json::from_str(json).unwrap();
+        for attack in PlayerStateEnum::ATTACKS.iter() {
+            assert!(
+                deserialized.moves.contains_key(attack),
+                "{:?} has no entry in the move table",
+                attack
+            );
+        }
+        for (state, definition) in deserialized.moves.iter() {
+            assert!(
+                definition.hit_state.is_take_hit(),
+                "{:?} puts the defender into {:?} but can only use one of the TakeHit states",
+                state,
+                definition.hit_state
+            );
+        }
+        return deserialized;
+    }
+
+    pub fn get(&self, state: PlayerStateEnum) -> Option<&MoveDefinition> {
+        self.moves.get(&state)
+    }
+
+    //The move the player is doing, but only on the frames it can actually hit
+    pub fn active_move(&self, player_state: &PlayerState) -> Option<&MoveDefinition> {
+        self.get(player_state.player_state)
+            .filter(|definition| definition.is_active(player_state.move_frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shipped_moves() -> serde_json::Value {
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/hitboxes/character_1_moves.json");
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn shipped_move_table_loads() {
+        let move_table = MoveTable::from_json(&shipped_moves().to_string());
+        for attack in PlayerStateEnum::ATTACKS.iter() {
+            assert!(move_table.get(*attack).is_some());
+        }
+        assert!(move_table.get(PlayerStateEnum::Throw).is_some());
+        assert!(move_table.get(PlayerStateEnum::Idle).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Super has no entry in the move table")]
+    fn missing_attack_is_rejected() {
+        let mut moves = shipped_moves();
+        moves["moves"].as_object_mut().unwrap().remove("Super");
+        MoveTable::from_json(&moves.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "LightAttack puts the defender into Juggle")]
+    fn hit_state_outside_take_hit_is_rejected() {
+        let mut moves = shipped_moves();
+        moves["moves"]["LightAttack"]["hit_state"] = serde_json::json!("Juggle");
+        MoveTable::from_json(&moves.to_string());
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_state_is_rejected() {
+        let mut moves = shipped_moves();
+        let light_attack = moves["moves"]["LightAttack"].clone();
+        moves["moves"]["FlyingAttack"] = light_attack;
+        MoveTable::from_json(&moves.to_string());
+    }
+}
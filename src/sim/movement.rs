@@ -0,0 +1,97 @@
+use crate::sim::*;
+
+//All of these are in subpixels, see SUBPIXELS_PER_PIXEL
+const GRAVITY: i32 = 1 * SUBPIXELS_PER_PIXEL;
+pub const FLOOR_HEIGHT: i32 = -250 * SUBPIXELS_PER_PIXEL;
+pub const PLAYER_SPEED: i32 = 5 * SUBPIXELS_PER_PIXEL;
+pub const PLAYER_DASH_SPEED: i32 = 15 * SUBPIXELS_PER_PIXEL;
+
+impl Fighter {
+    pub fn apply_movement(&mut self) {
+        let position = &mut self.position;
+        let player_state = &mut self.player_state;
+        position.x += player_state.x_velocity;
+        position.y += player_state.y_velocity;
+
+        match player_state.player_state {
+            PlayerStateEnum::Run => {}
+            PlayerStateEnum::Dash => {
+                player_state.y_velocity -= GRAVITY;
+                if position.y < FLOOR_HEIGHT {
+                    player_state.y_velocity = 0;
+                    position.y = FLOOR_HEIGHT;
+                }
+            }
+            PlayerStateEnum::TakeLightHit |
+            PlayerStateEnum::TakeMediumHit |
+            PlayerStateEnum::TakeHeavyHit |
+            PlayerStateEnum::BlockStun => {
+                player_state.y_velocity -= GRAVITY;
+                if position.y < FLOOR_HEIGHT {
+                    player_state.y_velocity = 0;
+                    position.y = FLOOR_HEIGHT;
+                }
+            }
+            PlayerStateEnum::AirHit => {
+                player_state.y_velocity -= GRAVITY;
+                if player_state.y_velocity < 0 {
+                    player_state.set_player_state_to_transition(PlayerStateEnum::Juggle);
+                }
+            }
+            PlayerStateEnum::Juggle => {
+                player_state.y_velocity -= GRAVITY;
+                if position.y < FLOOR_HEIGHT {
+                    if player_state.knockdown_frames > 0 {
+                        player_state.set_player_state_to_transition(PlayerStateEnum::Knockdown);
+                    } else {
+                        player_state.set_player_state_to_transition(PlayerStateEnum::Idle);
+                    }
+                    player_state.x_velocity = 0;
+                    player_state.y_velocity = 0;
+                    position.y = FLOOR_HEIGHT;
+                }
+            }
+
+            PlayerStateEnum::Jump => {
+                player_state.y_velocity -= GRAVITY;
+                if player_state.y_velocity < 0 {
+                    player_state.set_player_state_to_transition(PlayerStateEnum::Fall);
+                }
+            }
+            PlayerStateEnum::Fall => {
+                player_state.y_velocity -= GRAVITY;
+                if position.y < FLOOR_HEIGHT {
+                    player_state.set_player_state_to_transition(PlayerStateEnum::Landing);
+                    player_state.y_velocity = 0;
+                    position.y = FLOOR_HEIGHT;
+                }
+            }
+            //Attacks in the air keep falling along the jump, and end the moment we land
+            PlayerStateEnum::JumpLightAttack
+            | PlayerStateEnum::JumpMediumAttack
+            | PlayerStateEnum::JumpHeavyAttack => {
+                player_state.y_velocity -= GRAVITY;
+                if position.y < FLOOR_HEIGHT {
+                    player_state.set_player_state_to_transition(PlayerStateEnum::Landing);
+                    player_state.y_velocity = 0;
+                    position.y = FLOOR_HEIGHT;
+                }
+            }
+            PlayerStateEnum::Idle => {
+                if position.y > FLOOR_HEIGHT {
+                    player_state.set_player_state_to_transition(PlayerStateEnum::Fall);
+                }
+                if player_state.is_colliding == false {
+                    player_state.x_velocity = 0;
+                }
+            }
+            PlayerStateEnum::Death => {
+                if position.y < FLOOR_HEIGHT {
+                    player_state.y_velocity = 0;
+                    position.y = FLOOR_HEIGHT;
+                }
+            }
+            _ => {}
+        }
+    }
+}
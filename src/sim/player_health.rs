@@ -0,0 +1,24 @@
+#[derive(Default, Copy, Clone, Debug, Hash)]
+pub struct PlayerHealth {
+    pub health: usize,
+}
+
+impl PlayerHealth {
+    pub fn new() -> PlayerHealth {
+        PlayerHealth { health: 100 }
+    }
+
+    pub fn reset(&mut self) {
+        self.health = 100;
+    }
+
+    pub fn take_damage(&mut self, amount: usize) -> bool {
+        if amount > self.health {
+            self.health = 0;
+            return true;
+        } else {
+            self.health -= amount;
+            return false;
+        }
+    }
+}
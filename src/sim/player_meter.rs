@@ -0,0 +1,40 @@
+use crate::sim::*;
+
+//One bar of meter, what an EX move costs
+pub const METER_PER_BAR: usize = 50;
+pub const MAX_METER: usize = 2 * METER_PER_BAR;
+
+//Built up by hitting, getting hit and blocking, then spent on EX moves and supers
+#[derive(Default, Copy, Clone, Debug, Hash)]
+pub struct PlayerMeter {
+    pub meter: usize,
+}
+
+impl PlayerMeter {
+    pub fn new() -> PlayerMeter {
+        PlayerMeter { meter: 0 }
+    }
+
+    pub fn reset(&mut self) {
+        self.meter = 0;
+    }
+
+    pub fn gain(&mut self, amount: usize) {
+        self.meter = (self.meter + amount).min(MAX_METER);
+    }
+
+    pub fn spend(&mut self, amount: usize) {
+        self.meter = self.meter.saturating_sub(amount);
+    }
+}
+
+impl Fighter {
+    //How long to freeze the match for, on the first frame of a move that asks for it
+    pub fn super_freeze_to_start(&self, move_table: &MoveTable) -> Option<usize> {
+        let current_move = move_table.get(self.player_state.player_state)?;
+        if current_move.super_freeze == 0 || self.player_state.move_frame != 0 {
+            return None;
+        }
+        Some(current_move.super_freeze)
+    }
+}
@@ -0,0 +1,878 @@
+use crate::sim::*;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Hash, Reflect, Component, Deserialize)]
+#[reflect(Hash)]
+pub enum PlayerStateEnum {
+    Idle,
+    Run,
+    //Crouching down to jump, then in the air until Fall lands us in Landing
+    PreJump,
+    Jump,
+    HeavyAttack,
+    MediumAttack,
+    LightAttack,
+    Fall,
+    Landing,
+    TakeLightHit,
+    TakeMediumHit,
+    TakeHeavyHit,
+    Death,
+    Dash,
+    Block,
+    BlockStun,
+    Crouch,
+    CrouchBlock,
+    CrouchLightAttack,
+    CrouchMediumAttack,
+    CrouchHeavyAttack,
+    //Attacks done from a Jump or Fall, they last until we land
+    JumpLightAttack,
+    JumpMediumAttack,
+    JumpHeavyAttack,
+    //Knocked into the air by a hit, nothing but landing gets them out of it
+    Juggle,
+    //Knocked upwards by a hit that caught us in the air, we are juggled once we start falling again
+    AirHit,
+    //Reaching out to grab, ThrowHold once it has hold of the other fighter who is then Thrown
+    Throw,
+    ThrowHold,
+    Thrown,
+    //Throws whatever projectile its move data has
+    SpecialAbility,
+    //The stronger SpecialAbility, paid for with meter
+    ExSpecialAbility,
+    Super,
+    //Lying on the ground after a knockdown, then getting up from it either where we fell or rolling back
+    Knockdown,
+    WakeUp,
+    BackRoll,
+}
+
+#[derive(Copy, Clone, Debug, Reflect, Hash, Default)]
+pub struct AbilityTimer {
+    pub total_frames: usize,
+    pub current_frame: usize,
+    pub running: bool,
+}
+
+impl AbilityTimer {
+    pub fn new(total_frames: usize) -> AbilityTimer {
+        AbilityTimer {
+            total_frames,
+            current_frame: 0,
+            running: false,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.current_frame += 1;
+        if self.current_frame >= self.total_frames {
+            self.running = false;
+        }
+    }
+
+    pub fn running(&mut self) -> bool {
+        self.running
+    }
+
+    pub fn start(&mut self) {
+        self.current_frame = 0;
+        self.running = true;
+    }
+
+    pub fn reset(&mut self) {
+        self.current_frame = 0;
+        self.running = false;
+    }
+}
+
+//The newest action pressed while it could not come out yet, empty once frames_left hits 0
+#[derive(Copy, Clone, Debug, Reflect, Hash, Default)]
+pub struct InputBuffer {
+    pub action: PlayerStateEnum,
+    pub frames_left: usize,
+}
+
+impl InputBuffer {
+    //The frame it was pressed on counts too, so it lives one frame past the window
+    pub fn buffer(&mut self, action: PlayerStateEnum, window: usize) {
+        self.action = action;
+        self.frames_left = window + 1;
+    }
+
+    pub fn tick(&mut self) {
+        self.frames_left = self.frames_left.saturating_sub(1);
+    }
+
+    pub fn action(&self) -> Option<PlayerStateEnum> {
+        if self.frames_left > 0 {
+            return Some(self.action);
+        }
+        None
+    }
+
+    pub fn clear(&mut self) {
+        self.frames_left = 0;
+    }
+}
+
+impl PlayerStateEnum {
+    pub fn to_string(&self) -> String {
+        match self {
+            PlayerStateEnum::Idle => String::from("Idle"),
+            PlayerStateEnum::Run => String::from("Run"),
+            PlayerStateEnum::PreJump => String::from("Crouch"),
+            PlayerStateEnum::Jump => String::from("Jump"),
+            PlayerStateEnum::HeavyAttack => String::from("HeavyAttack"),
+            PlayerStateEnum::MediumAttack => String::from("MediumAttack"),
+            PlayerStateEnum::LightAttack => String::from("LightAttack"),
+            PlayerStateEnum::Fall => String::from("Fall"),
+            PlayerStateEnum::Landing => String::from("Crouch"),
+            PlayerStateEnum::TakeLightHit => String::from("TakeHit"),
+            PlayerStateEnum::TakeMediumHit => String::from("TakeHit"),
+            PlayerStateEnum::TakeHeavyHit => String::from("TakeHit"),
+            PlayerStateEnum::Death => String::from("Death"),
+            PlayerStateEnum::Dash => String::from("Dash"),
+            PlayerStateEnum::Block => String::from("Block"),
+            PlayerStateEnum::BlockStun => String::from("Block"),
+            PlayerStateEnum::Crouch => String::from("Crouch"),
+            PlayerStateEnum::CrouchBlock => String::from("CrouchBlock"),
+            PlayerStateEnum::CrouchLightAttack => String::from("CrouchLightAttack"),
+            PlayerStateEnum::CrouchMediumAttack => String::from("CrouchMediumAttack"),
+            PlayerStateEnum::CrouchHeavyAttack => String::from("CrouchHeavyAttack"),
+            PlayerStateEnum::JumpLightAttack => String::from("JumpLightAttack"),
+            PlayerStateEnum::JumpMediumAttack => String::from("JumpMediumAttack"),
+            PlayerStateEnum::JumpHeavyAttack => String::from("JumpHeavyAttack"),
+            PlayerStateEnum::Juggle => String::from("TakeHit"),
+            PlayerStateEnum::AirHit => String::from("TakeHit"),
+            PlayerStateEnum::Throw => String::from("Throw"),
+            PlayerStateEnum::ThrowHold => String::from("ThrowHold"),
+            PlayerStateEnum::Thrown => String::from("TakeHit"),
+            PlayerStateEnum::SpecialAbility => String::from("SpecialAbility"),
+            PlayerStateEnum::ExSpecialAbility => String::from("ExSpecialAbility"),
+            PlayerStateEnum::Super => String::from("Super"),
+            PlayerStateEnum::Knockdown => String::from("Knockdown"),
+            PlayerStateEnum::WakeUp => String::from("WakeUp"),
+            PlayerStateEnum::BackRoll => String::from("BackRoll"),
+        }
+    }
+
+    pub fn sprite_sheet(&self) -> String {
+        format!("sprites/{}.png", self.to_string())
+    }
+
+    //The same attack done from a crouch, anything that has no crouching version stays as it is
+    pub fn crouching(&self) -> PlayerStateEnum {
+        match self {
+            PlayerStateEnum::LightAttack => PlayerStateEnum::CrouchLightAttack,
+            PlayerStateEnum::MediumAttack => PlayerStateEnum::CrouchMediumAttack,
+            PlayerStateEnum::HeavyAttack => PlayerStateEnum::CrouchHeavyAttack,
+            _ => *self,
+        }
+    }
+
+    //The same attack done in the air, anything that has no jumping version stays as it is
+    pub fn jumping(&self) -> PlayerStateEnum {
+        match self {
+            PlayerStateEnum::LightAttack => PlayerStateEnum::JumpLightAttack,
+            PlayerStateEnum::MediumAttack => PlayerStateEnum::JumpMediumAttack,
+            PlayerStateEnum::HeavyAttack => PlayerStateEnum::JumpHeavyAttack,
+            _ => *self,
+        }
+    }
+
+    //What comes out instead when there is not enough meter for the move
+    pub fn without_meter(&self) -> PlayerStateEnum {
+        match self {
+            PlayerStateEnum::ExSpecialAbility => PlayerStateEnum::SpecialAbility,
+            PlayerStateEnum::Super => PlayerStateEnum::SpecialAbility,
+            _ => *self,
+        }
+    }
+
+    //The states a strike can put the defender into, the rest of getting hit comes out of these
+    pub fn is_take_hit(&self) -> bool {
+        *self == PlayerStateEnum::TakeLightHit
+            || *self == PlayerStateEnum::TakeMediumHit
+            || *self == PlayerStateEnum::TakeHeavyHit
+    }
+}
+
+impl Default for PlayerStateEnum {
+    fn default() -> PlayerStateEnum {
+        PlayerStateEnum::Idle
+    }
+}
+
+#[derive(Default, Reflect, Clone, Component, Hash, Copy, Debug)]
+pub struct PlayerState {
+    pub player_id: usize,
+    pub player_state: PlayerStateEnum,
+    pub desired_player_state: PlayerStateEnum,
+    //Which drawing of the animation is on screen, only the renderer cares about it
+    pub current_sprite_index: usize,
+    //How far into the animation we are in frames, this is what picks the colliders
+    pub animation_frame: usize,
+    pub x_velocity: i32,
+    pub y_velocity: i32,
+    pub is_colliding: bool,
+    pub state_is_dirty: bool,
+    //Kept up to date by FightSim, only one of our projectiles can be out at a time
+    pub has_projectile: bool,
+    //Kept up to date by Fighter, how much meter moves are allowed to cost right now
+    pub available_meter: usize,
+    //Only one dash per jump is allowed in the air
+    pub has_dahsed: bool,
+    pub dash_timer: AbilityTimer,
+    //Times the prejump and the landing recovery of a jump
+    pub jump_timer: AbilityTimer,
+    //How many more jumps we have before we have to land, see JumpData::air_jumps
+    pub air_jumps_left: usize,
+    //How many frames the current state has been going for, attacks look their frame data up with it
+    pub move_frame: usize,
+    pub hit_stun_timer: AbilityTimer,
+    pub block_stun_timer: AbilityTimer,
+    //How fast the last hit, block or back roll sends us sliding away, in subpixels
+    pub pushback_speed: i32,
+    //How fast the last hit knocked us upwards if it juggled us, in subpixels
+    pub launch_speed: i32,
+    //The current move has already hit or been blocked, so it can not connect again
+    pub move_connected: bool,
+    //While we are Thrown, how long we still have to break the throw and if we did
+    pub throw_tech_timer: AbilityTimer,
+    pub throw_teched: bool,
+    //How long we lie down for once the hit that knocked us down lands us, 0 lands us on our feet
+    pub knockdown_frames: usize,
+    //Times whichever part of the knockdown we are in
+    pub knockdown_timer: AbilityTimer,
+    pub input_buffer: InputBuffer,
+}
+
+impl PlayerState {
+    pub fn new(player_id: usize, player_state: PlayerStateEnum) -> PlayerState {
+        PlayerState {
+            player_id,
+            player_state,
+            desired_player_state: player_state,
+            current_sprite_index: 0,
+            animation_frame: 0,
+            x_velocity: 0,
+            y_velocity: 0,
+            is_colliding: false,
+            state_is_dirty: true,
+            has_projectile: false,
+            available_meter: 0,
+            has_dahsed: false,
+            dash_timer: AbilityTimer::new(35),
+            jump_timer: AbilityTimer::default(),
+            air_jumps_left: 0,
+            move_frame: 0,
+            hit_stun_timer: AbilityTimer::default(),
+            block_stun_timer: AbilityTimer::default(),
+            pushback_speed: 0,
+            launch_speed: 0,
+            move_connected: false,
+            throw_tech_timer: AbilityTimer::new(THROW_TECH_FRAMES),
+            throw_teched: false,
+            knockdown_frames: 0,
+            knockdown_timer: AbilityTimer::default(),
+            input_buffer: InputBuffer::default(),
+        }
+    }
+
+    pub fn attempt_to_transition_state(&mut self) -> bool {
+        let copy_of_initial_state = self.player_state.clone();
+        match self.player_state {
+            PlayerStateEnum::Idle => {
+                self.player_state = self.desired_player_state;
+            }
+            PlayerStateEnum::Run => {
+                self.player_state = self.desired_player_state;
+            }
+            PlayerStateEnum::PreJump => {}
+            PlayerStateEnum::Jump => {}
+            //Attacks only end early down the cancel routes in their move data, see can_start
+            PlayerStateEnum::HeavyAttack => {}
+            PlayerStateEnum::LightAttack => {}
+            PlayerStateEnum::MediumAttack => {}
+            PlayerStateEnum::Fall => {}
+            PlayerStateEnum::Landing => {}
+            PlayerStateEnum::TakeLightHit => {}
+            PlayerStateEnum::TakeMediumHit => {}
+            PlayerStateEnum::TakeHeavyHit => {}
+            PlayerStateEnum::Death => {}
+            PlayerStateEnum::Dash => {}
+            PlayerStateEnum::Block => {
+                self.player_state = self.desired_player_state;
+            }
+            PlayerStateEnum::BlockStun => {}
+            PlayerStateEnum::Crouch => {
+                self.player_state = self.desired_player_state;
+            }
+            PlayerStateEnum::CrouchBlock => {
+                self.player_state = self.desired_player_state;
+            }
+            PlayerStateEnum::CrouchLightAttack => {}
+            PlayerStateEnum::CrouchMediumAttack => {}
+            PlayerStateEnum::CrouchHeavyAttack => {}
+            PlayerStateEnum::JumpLightAttack => {}
+            PlayerStateEnum::JumpMediumAttack => {}
+            PlayerStateEnum::JumpHeavyAttack => {}
+            PlayerStateEnum::Juggle => {}
+            PlayerStateEnum::AirHit => {}
+            PlayerStateEnum::Throw => {}
+            PlayerStateEnum::ThrowHold => {}
+            PlayerStateEnum::Thrown => {}
+            PlayerStateEnum::SpecialAbility => {}
+            PlayerStateEnum::ExSpecialAbility => {}
+            PlayerStateEnum::Super => {}
+            PlayerStateEnum::Knockdown => {}
+            PlayerStateEnum::WakeUp => {}
+            PlayerStateEnum::BackRoll => {}
+        }
+        return copy_of_initial_state != self.player_state;
+    }
+
+    pub fn reset_state(&mut self) {
+        self.current_sprite_index = 0;
+        self.animation_frame = 0;
+    }
+
+    pub fn hard_reset(&mut self) {
+        self.player_state = PlayerStateEnum::Idle;
+        self.desired_player_state = PlayerStateEnum::Idle;
+        self.current_sprite_index = 0;
+        self.animation_frame = 0;
+        self.x_velocity = 0;
+        self.y_velocity = 0;
+        self.is_colliding = false;
+        self.state_is_dirty = true;
+        self.has_projectile = false;
+        self.available_meter = 0;
+        self.has_dahsed = false;
+        self.jump_timer.reset();
+        self.air_jumps_left = 0;
+        self.move_frame = 0;
+        self.hit_stun_timer.reset();
+        self.block_stun_timer.reset();
+        self.move_connected = false;
+        self.throw_tech_timer.reset();
+        self.throw_teched = false;
+        self.knockdown_frames = 0;
+        self.knockdown_timer.reset();
+        self.input_buffer.clear();
+    }
+
+    pub fn animation_finished(&mut self) -> PlayerStateEnum {
+        match self.player_state {
+            PlayerStateEnum::Idle => PlayerStateEnum::Idle,
+            PlayerStateEnum::Run => PlayerStateEnum::Run,
+            PlayerStateEnum::PreJump => PlayerStateEnum::PreJump,
+            PlayerStateEnum::Jump => PlayerStateEnum::Jump,
+            //Attacks and hits last as long as their frame data says, not as long as their animation
+            PlayerStateEnum::HeavyAttack => PlayerStateEnum::HeavyAttack,
+            PlayerStateEnum::MediumAttack => PlayerStateEnum::MediumAttack,
+            PlayerStateEnum::LightAttack => PlayerStateEnum::LightAttack,
+            PlayerStateEnum::Fall => PlayerStateEnum::Fall,
+            PlayerStateEnum::Landing => PlayerStateEnum::Landing,
+            PlayerStateEnum::TakeLightHit => PlayerStateEnum::TakeLightHit,
+            PlayerStateEnum::TakeMediumHit => PlayerStateEnum::TakeMediumHit,
+            PlayerStateEnum::TakeHeavyHit => PlayerStateEnum::TakeHeavyHit,
+            PlayerStateEnum::Death => PlayerStateEnum::Death,
+            PlayerStateEnum::Dash => PlayerStateEnum::Idle,
+            PlayerStateEnum::Block => PlayerStateEnum::Block,
+            PlayerStateEnum::BlockStun => PlayerStateEnum::BlockStun,
+            PlayerStateEnum::Crouch => PlayerStateEnum::Crouch,
+            PlayerStateEnum::CrouchBlock => PlayerStateEnum::CrouchBlock,
+            PlayerStateEnum::CrouchLightAttack => PlayerStateEnum::CrouchLightAttack,
+            PlayerStateEnum::CrouchMediumAttack => PlayerStateEnum::CrouchMediumAttack,
+            PlayerStateEnum::CrouchHeavyAttack => PlayerStateEnum::CrouchHeavyAttack,
+            PlayerStateEnum::JumpLightAttack => PlayerStateEnum::JumpLightAttack,
+            PlayerStateEnum::JumpMediumAttack => PlayerStateEnum::JumpMediumAttack,
+            PlayerStateEnum::JumpHeavyAttack => PlayerStateEnum::JumpHeavyAttack,
+            PlayerStateEnum::Juggle => PlayerStateEnum::Juggle,
+            PlayerStateEnum::AirHit => PlayerStateEnum::AirHit,
+            PlayerStateEnum::Throw => PlayerStateEnum::Throw,
+            PlayerStateEnum::ThrowHold => PlayerStateEnum::ThrowHold,
+            PlayerStateEnum::Thrown => PlayerStateEnum::Thrown,
+            PlayerStateEnum::SpecialAbility => PlayerStateEnum::SpecialAbility,
+            PlayerStateEnum::ExSpecialAbility => PlayerStateEnum::ExSpecialAbility,
+            PlayerStateEnum::Super => PlayerStateEnum::Super,
+            PlayerStateEnum::Knockdown => PlayerStateEnum::Knockdown,
+            PlayerStateEnum::WakeUp => PlayerStateEnum::WakeUp,
+            PlayerStateEnum::BackRoll => PlayerStateEnum::BackRoll,
+        }
+    }
+
+    //These last as long as their frame data or timers say, not as long as their animation
+    pub fn holds_last_keyframe(&self) -> bool {
+        self.is_attacking()
+            || self.is_throwing()
+            || self.player_state == PlayerStateEnum::Knockdown
+            || self.player_state == PlayerStateEnum::WakeUp
+    }
+
+    //Hitstun and blockstun do not protect anyone, that is what lets hits combo and blocks string together.
+    //Both sides of a throw are left alone until it is over, and nobody can be hit while knocked down
+    pub fn can_take_a_hit(&self) -> bool {
+        return self.player_state != PlayerStateEnum::Death
+            && self.is_knocked_down() == false
+            && self.desired_player_state != PlayerStateEnum::Knockdown
+            && self.desired_player_state != PlayerStateEnum::Death
+            && self.player_state != PlayerStateEnum::ThrowHold
+            && self.desired_player_state != PlayerStateEnum::ThrowHold
+            && self.player_state != PlayerStateEnum::Thrown
+            && self.desired_player_state != PlayerStateEnum::Thrown;
+    }
+
+    pub fn is_in_hit_stun(&self) -> bool {
+        self.player_state.is_take_hit()
+    }
+
+    pub fn set_player_state_to_transition(&mut self, new_player_state: PlayerStateEnum) {
+        self.desired_player_state = new_player_state;
+        self.state_is_dirty = true;
+    }
+
+    pub fn tick_timers(&mut self) {
+        if self.hit_stun_timer.running() {
+            self.hit_stun_timer.tick();
+        }
+        if self.dash_timer.running() {
+            self.dash_timer.tick();
+        }
+        if self.block_stun_timer.running() {
+            self.block_stun_timer.tick();
+        }
+        if self.throw_tech_timer.running() {
+            self.throw_tech_timer.tick();
+        }
+        if self.knockdown_timer.running() {
+            self.knockdown_timer.tick();
+        }
+        if self.jump_timer.running() {
+            self.jump_timer.tick();
+        }
+    }
+
+    //Remember the newest attack, jump or dash press, so it can still come out if we are busy right now
+    pub fn buffer_pressed_action(
+        &mut self,
+        input_history: &InputHistory,
+        screen_side: ScreenSideEnum,
+        move_table: &MoveTable,
+    ) {
+        let window = move_table.input_buffer;
+        //Same order read_input has always checked them in, so the last one wins when several land together
+        let actions = [
+            (InputButton::Jump, PlayerStateEnum::Jump),
+            (InputButton::HeavyAttack, PlayerStateEnum::HeavyAttack),
+            (InputButton::LightAttack, PlayerStateEnum::LightAttack),
+            (InputButton::MediumAttack, PlayerStateEnum::MediumAttack),
+            (InputButton::Dash, PlayerStateEnum::Dash),
+        ];
+        for (button, action) in actions {
+            if input_history.pressed_this_frame(button) {
+                self.input_buffer.buffer(action, window);
+            }
+        }
+        if input_history.pressed_throw() {
+            self.input_buffer.buffer(PlayerStateEnum::Throw, window);
+        }
+        //Special only comes out of a motion, 236 for the special itself, with heavy held for the EX version,
+        //and 41236 for the super
+        if input_history.pressed_this_frame(InputButton::SpecialAbility) {
+            match input_history.completed_motion(screen_side) {
+                Some(MotionInput::HalfCircleForward) => {
+                    self.input_buffer.buffer(PlayerStateEnum::Super, window);
+                }
+                Some(MotionInput::QuarterCircleForward)
+                    if input_history.held(InputButton::HeavyAttack) =>
+                {
+                    self.input_buffer
+                        .buffer(PlayerStateEnum::ExSpecialAbility, window);
+                }
+                Some(MotionInput::QuarterCircleForward) => {
+                    self.input_buffer
+                        .buffer(PlayerStateEnum::SpecialAbility, window);
+                }
+                _ => {}
+            }
+        }
+
+        //Tapping a direction twice dashes too, the same as holding it and pressing dash
+        if input_history.completed(MotionInput::DoubleTapForward, screen_side)
+            || input_history.completed(MotionInput::DoubleTapBack, screen_side)
+        {
+            self.input_buffer.buffer(PlayerStateEnum::Dash, window);
+        }
+    }
+
+    //Judged against the state we are about to be in, so something ending this frame lets the next thing out straight away
+    fn can_start(
+        &mut self,
+        action: PlayerStateEnum,
+        input: &InputEvents,
+        move_table: &MoveTable,
+    ) -> bool {
+        let state = if self.state_is_dirty {
+            self.desired_player_state
+        } else {
+            self.player_state
+        };
+        //Move data lists jump cancels as Jump, a jump off the ground comes out as PreJump like any other
+        let cancel_route = if action == PlayerStateEnum::PreJump {
+            PlayerStateEnum::Jump
+        } else {
+            action
+        };
+        let is_cancel = self.state_is_dirty == false
+            && move_table.get(state).map_or(false, |current_move| {
+                current_move.can_cancel_into(cancel_route, self.move_frame, self.move_connected)
+            });
+        let is_free = state == PlayerStateEnum::Idle
+            || state == PlayerStateEnum::Run
+            || state == PlayerStateEnum::Block
+            || state == PlayerStateEnum::Crouch
+            || state == PlayerStateEnum::CrouchBlock
+            || is_cancel;
+        let is_in_the_air = state == PlayerStateEnum::Jump || state == PlayerStateEnum::Fall;
+        match action {
+            PlayerStateEnum::PreJump => is_free,
+            PlayerStateEnum::Jump => is_in_the_air && self.air_jumps_left > 0,
+            PlayerStateEnum::HeavyAttack
+            | PlayerStateEnum::CrouchHeavyAttack
+            | PlayerStateEnum::LightAttack
+            | PlayerStateEnum::CrouchLightAttack
+            | PlayerStateEnum::MediumAttack
+            | PlayerStateEnum::CrouchMediumAttack
+            | PlayerStateEnum::Throw => is_free,
+            //One attack per jump, as they last until we land
+            PlayerStateEnum::JumpLightAttack
+            | PlayerStateEnum::JumpMediumAttack
+            | PlayerStateEnum::JumpHeavyAttack => is_in_the_air,
+            PlayerStateEnum::SpecialAbility | PlayerStateEnum::ExSpecialAbility => {
+                is_free && self.has_projectile == false && self.can_afford(action, move_table)
+            }
+            PlayerStateEnum::Super => is_free && self.can_afford(action, move_table),
+            PlayerStateEnum::Dash => {
+                let can_air_dash =
+                    is_in_the_air && move_table.jump.air_dash && self.has_dahsed == false;
+                (is_free || can_air_dash)
+                    && input.left_right_axis != 0
+                    && self.dash_timer.running() == false
+            }
+            _ => false,
+        }
+    }
+
+    fn can_afford(&self, action: PlayerStateEnum, move_table: &MoveTable) -> bool {
+        move_table.get(action).map_or(true, |definition| {
+            definition.meter_cost <= self.available_meter
+        })
+    }
+
+    //Queue up whatever state the players input is asking for, only if nothing else has already claimed this frame
+    pub fn read_input(
+        &mut self,
+        input_history: &InputHistory,
+        screen_side: ScreenSideEnum,
+        opponent_is_attacking: bool,
+        move_table: &MoveTable,
+    ) {
+        self.input_buffer.tick();
+        self.buffer_pressed_action(input_history, screen_side, move_table);
+        if self.state_is_dirty {
+            return;
+        }
+        let input = input_history.current();
+        let is_holding_down = input.up_down_axis > 0;
+
+        if self.read_wake_up(&input, screen_side, &move_table.wake_up) {
+            return;
+        }
+        if self.read_jump() {
+            return;
+        }
+
+        //Neither side of a throw gets to act, all the one being thrown can do is try and break it.
+        //A throw pressed a few frames before being grabbed still counts, it waits in the buffer like any other press
+        if self.player_state == PlayerStateEnum::Thrown {
+            if self.throw_tech_timer.running()
+                && self.input_buffer.action() == Some(PlayerStateEnum::Throw)
+            {
+                self.throw_teched = true;
+                self.input_buffer.clear();
+            }
+            return;
+        }
+        if self.player_state == PlayerStateEnum::ThrowHold {
+            return;
+        }
+
+        //Attacks run for exactly their frame data, then drop back to whatever stance they started from,
+        //unless they get cancelled into something else first
+        if let Some(current_move) = move_table.get(self.player_state) {
+            if self.move_frame < current_move.total_frames() {
+                self.start_pressed_actions(&input, is_holding_down, move_table);
+                return;
+            }
+            //Only landing ends an attack in the air, see apply_movement
+            if self.is_jump_attacking() {
+                return;
+            }
+            if self.is_crouching() {
+                self.set_player_state_to_transition(PlayerStateEnum::Crouch);
+            } else {
+                self.set_player_state_to_transition(PlayerStateEnum::Idle);
+            }
+        }
+
+        if self.is_juggled() {
+            return;
+        }
+
+        if self.is_in_hit_stun() {
+            if self.hit_stun_timer.running() {
+                return;
+            }
+            self.set_player_state_to_transition(PlayerStateEnum::Idle);
+        }
+
+        if self.player_state == PlayerStateEnum::BlockStun {
+            if self.block_stun_timer.running() {
+                return;
+            }
+            self.set_player_state_to_transition(PlayerStateEnum::Idle);
+        }
+
+        //Holding back while the other player is swinging at you stops you walking away and raises your guard,
+        //holding down-back keeps it low
+        let is_guarding =
+            opponent_is_attacking && input.left_right_axis as i32 == screen_side.back_direction();
+        if is_guarding {
+            let guard = if is_holding_down {
+                PlayerStateEnum::CrouchBlock
+            } else {
+                PlayerStateEnum::Block
+            };
+            if self.player_state != guard
+                && (self.player_state == PlayerStateEnum::Idle
+                    || self.player_state == PlayerStateEnum::Run
+                    || self.player_state == PlayerStateEnum::BlockStun
+                    || self.player_state == PlayerStateEnum::Block
+                    || self.player_state == PlayerStateEnum::Crouch
+                    || self.player_state == PlayerStateEnum::CrouchBlock)
+            {
+                self.set_player_state_to_transition(guard);
+            }
+        } else if self.player_state == PlayerStateEnum::Block
+            || self.player_state == PlayerStateEnum::CrouchBlock
+        {
+            self.set_player_state_to_transition(PlayerStateEnum::Idle);
+        } else if is_holding_down {
+            if self.player_state == PlayerStateEnum::Idle
+                || self.player_state == PlayerStateEnum::Run
+            {
+                self.set_player_state_to_transition(PlayerStateEnum::Crouch);
+            }
+        } else if self.player_state == PlayerStateEnum::Crouch {
+            self.set_player_state_to_transition(PlayerStateEnum::Idle);
+        }
+
+        if input.left_right_axis != 0 && is_guarding == false && is_holding_down == false {
+            if self.player_state == PlayerStateEnum::Idle {
+                self.set_player_state_to_transition(PlayerStateEnum::Run);
+            }
+        } else {
+            if self.player_state == PlayerStateEnum::Run {
+                self.set_player_state_to_transition(PlayerStateEnum::Idle);
+            }
+        }
+
+        self.start_pressed_actions(&input, is_holding_down, move_table);
+    }
+
+    fn start_pressed_actions(
+        &mut self,
+        input: &InputEvents,
+        is_holding_down: bool,
+        move_table: &MoveTable,
+    ) {
+        //Holding up keeps on jumping, it does not need a fresh press
+        if input.jump_was_pressed == true
+            && self.can_start(PlayerStateEnum::PreJump, input, move_table)
+        {
+            self.set_player_state_to_transition(PlayerStateEnum::PreJump);
+        }
+
+        //Attacks and dashes need a fresh press, which waits in the buffer until the first frame it is allowed out
+        if let Some(action) = self.input_buffer.action() {
+            //A jump pressed in the air is a double jump, on the ground it has to crouch down first
+            let action = if self.is_jumping() {
+                action.jumping()
+            } else if action == PlayerStateEnum::Jump {
+                PlayerStateEnum::PreJump
+            } else if is_holding_down {
+                action.crouching()
+            } else {
+                action
+            };
+            let action = if self.can_afford(action, move_table) {
+                action
+            } else {
+                action.without_meter()
+            };
+            if self.can_start(action, input, move_table) {
+                self.set_player_state_to_transition(action);
+                self.input_buffer.clear();
+            }
+        }
+    }
+
+    //Apply everything that happens on the first frame of the desired state, and make it the current one
+    pub fn enter_desired_state(
+        &mut self,
+        input: &InputEvents,
+        screen_side: ScreenSideEnum,
+        jump: &JumpData,
+    ) {
+        self.current_sprite_index = 0;
+        self.animation_frame = 0;
+        self.move_frame = 0;
+        self.move_connected = false;
+        match self.desired_player_state {
+            PlayerStateEnum::Idle => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::Run => {
+                self.x_velocity = PLAYER_SPEED * input.left_right_axis as i32;
+            }
+            PlayerStateEnum::PreJump => {
+                self.x_velocity = 0;
+                self.jump_timer = AbilityTimer::new(jump.prejump);
+                self.jump_timer.start();
+                self.air_jumps_left = jump.air_jumps;
+                self.has_dahsed = false;
+                //The press that started this jump is used up, it must not double jump us the moment we leave the ground
+                if self.input_buffer.action() == Some(PlayerStateEnum::Jump) {
+                    self.input_buffer.clear();
+                }
+            }
+            //Leaving the ground or jumping again in the air, either way whatever direction is held right now picks the arc
+            PlayerStateEnum::Jump => {
+                if self.player_state != PlayerStateEnum::PreJump {
+                    self.air_jumps_left = self.air_jumps_left.saturating_sub(1);
+                }
+                self.x_velocity = jump.x_speed(input.left_right_axis as i32, screen_side);
+                self.y_velocity = jump.y_speed();
+            }
+            PlayerStateEnum::HeavyAttack => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::MediumAttack => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::LightAttack => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::Fall => {}
+            PlayerStateEnum::Landing => {
+                self.x_velocity = 0;
+                self.jump_timer = AbilityTimer::new(jump.landing_recovery);
+                self.jump_timer.start();
+            }
+            PlayerStateEnum::TakeLightHit
+            | PlayerStateEnum::TakeMediumHit
+            | PlayerStateEnum::TakeHeavyHit => {
+                self.x_velocity = self.pushback_speed * screen_side.back_direction();
+                self.hit_stun_timer.start();
+            }
+            PlayerStateEnum::Death => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::Dash => {
+                if self.is_jumping() {
+                    self.has_dahsed = true;
+                }
+                self.x_velocity = PLAYER_DASH_SPEED * input.left_right_axis as i32;
+                self.y_velocity = 0;
+                self.dash_timer.start();
+            }
+            PlayerStateEnum::Block => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::BlockStun => {
+                self.x_velocity = self.pushback_speed * screen_side.back_direction();
+                self.block_stun_timer.start();
+            }
+            PlayerStateEnum::Crouch => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::CrouchBlock => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::CrouchLightAttack => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::CrouchMediumAttack => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::CrouchHeavyAttack => {
+                self.x_velocity = 0;
+            }
+            //Carries on along whatever arc the jump was already on
+            PlayerStateEnum::JumpLightAttack
+            | PlayerStateEnum::JumpMediumAttack
+            | PlayerStateEnum::JumpHeavyAttack => {}
+            PlayerStateEnum::Juggle => {
+                self.x_velocity = self.pushback_speed * screen_side.back_direction();
+                self.y_velocity = self.launch_speed;
+            }
+            //The launch is used up here, so falling into the Juggle afterwards does not send us back up
+            PlayerStateEnum::AirHit => {
+                self.x_velocity = self.pushback_speed * screen_side.back_direction();
+                self.y_velocity = self.launch_speed;
+                self.launch_speed = 0;
+            }
+            PlayerStateEnum::Throw => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::ThrowHold => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::Thrown => {
+                self.x_velocity = 0;
+                self.throw_teched = false;
+                self.throw_tech_timer.start();
+            }
+            PlayerStateEnum::SpecialAbility => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::ExSpecialAbility => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::Super => {
+                self.x_velocity = 0;
+            }
+            PlayerStateEnum::Knockdown => {
+                self.x_velocity = 0;
+                self.y_velocity = 0;
+                self.knockdown_timer = AbilityTimer::new(self.knockdown_frames);
+                self.knockdown_timer.start();
+                self.knockdown_frames = 0;
+            }
+            PlayerStateEnum::WakeUp => {
+                self.x_velocity = 0;
+                self.knockdown_timer.start();
+            }
+            PlayerStateEnum::BackRoll => {
+                self.x_velocity = self.pushback_speed * screen_side.back_direction();
+                self.knockdown_timer.start();
+            }
+        }
+        self.player_state = self.desired_player_state;
+    }
+}
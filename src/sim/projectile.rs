@@ -0,0 +1,178 @@
+use crate::sim::*;
+use serde::Deserialize;
+
+//What a move throws out, every distance is in pixels and every duration in frames
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectileDefinition {
+    //Names both its colliders and the image it is drawn with
+    pub animation: String,
+    //How far it travels forward every frame
+    pub speed: i32,
+    pub lifetime: usize,
+    //Where it appears relative to the fighter that threw it, as if they were facing right
+    pub spawn_offset: (i32, i32),
+}
+
+//Something a fighter threw, it hits and is blocked using the frame data of the move that threw it
+#[derive(Copy, Clone, Debug, Hash)]
+pub struct Projectile {
+    pub owner: usize,
+    pub move_state: PlayerStateEnum,
+    pub position: Position,
+    pub x_velocity: i32,
+    pub screen_side: ScreenSideEnum,
+    //How many frames it has been out for, it also picks the colliders
+    pub age: usize,
+}
+
+impl Projectile {
+    pub fn attack<'a>(&self, move_table: &'a MoveTable) -> &'a MoveDefinition {
+        move_table
+            .get(self.move_state)
+            .expect("A projectile has to come from a move in the move table")
+    }
+
+    pub fn definition<'a>(&self, move_table: &'a MoveTable) -> &'a ProjectileDefinition {
+        self.attack(move_table)
+            .projectile
+            .as_ref()
+            .expect("A projectile has to come from a move that throws one")
+    }
+
+    //Projectile animations loop for as long as they are out
+    fn colliders<'a>(
+        &self,
+        collider_boxes: &'a ColliderSetComponent,
+        move_table: &MoveTable,
+    ) -> &'a Vec<Collider> {
+        let animation = &self.definition(move_table).animation;
+        let animation_frame = self.age % collider_boxes.animation_length(animation);
+        collider_boxes.colliders_at(animation, animation_frame)
+    }
+
+    pub fn advance(&mut self) {
+        self.position.x += self.x_velocity;
+        self.age += 1;
+    }
+
+    pub fn is_expired(&self, move_table: &MoveTable) -> bool {
+        self.age >= self.definition(move_table).lifetime
+    }
+}
+
+impl Fighter {
+    //A move that throws a projectile lets go of it on its first active frame
+    pub fn projectile_to_spawn(&self, move_table: &MoveTable) -> Option<Projectile> {
+        let attack = move_table.get(self.player_state.player_state)?;
+        let projectile = attack.projectile.as_ref()?;
+        if self.player_state.move_frame != attack.startup {
+            return None;
+        }
+        let forward = -self.screen_side.back_direction();
+        Some(Projectile {
+            owner: self.player_state.player_id,
+            move_state: self.player_state.player_state,
+            position: Position::new(
+                self.position.x + projectile.spawn_offset.0 * SUBPIXELS_PER_PIXEL * forward,
+                self.position.y + projectile.spawn_offset.1 * SUBPIXELS_PER_PIXEL,
+            ),
+            x_velocity: projectile.speed * SUBPIXELS_PER_PIXEL * forward,
+            screen_side: self.screen_side,
+            age: 0,
+        })
+    }
+}
+
+//Where a set of colliders is standing and which way it faces
+type Placement = (Position, ScreenSideEnum);
+
+fn any_overlap(
+    colliders_1: &[Collider],
+    collider_type_1: ColliderType,
+    (position_1, screen_side_1): Placement,
+    colliders_2: &[Collider],
+    collider_type_2: ColliderType,
+    (position_2, screen_side_2): Placement,
+) -> bool {
+    colliders_1
+        .iter()
+        .filter(|collider| collider.collider_type == collider_type_1)
+        .any(|collider_1| {
+            colliders_2
+                .iter()
+                .filter(|collider| collider.collider_type == collider_type_2)
+                .any(|collider_2| {
+                    collider_1.overlaps(
+                        collider_1.center(position_1, screen_side_1),
+                        collider_2,
+                        collider_2.center(position_2, screen_side_2),
+                    )
+                })
+        })
+}
+
+//Projectiles from the two players that touch cancel each other out, the rest strike the fighter they reach
+//just as the move that threw them would. Returns how many frames of hit stop a projectile that connected asks for
+pub fn resolve_projectile_collisions(
+    collider_boxes: &ColliderSetComponent,
+    move_table: &MoveTable,
+    projectiles: &mut Vec<Projectile>,
+    fighters: &mut [Fighter; 2],
+) -> Option<usize> {
+    let mut destroyed = vec![false; projectiles.len()];
+    for i in 0..projectiles.len() {
+        for j in (i + 1)..projectiles.len() {
+            let (projectile_1, projectile_2) = (&projectiles[i], &projectiles[j]);
+            if projectile_1.owner == projectile_2.owner || destroyed[i] || destroyed[j] {
+                continue;
+            }
+            if any_overlap(
+                projectile_1.colliders(collider_boxes, move_table),
+                ColliderType::HurtBox,
+                (projectile_1.position, projectile_1.screen_side),
+                projectile_2.colliders(collider_boxes, move_table),
+                ColliderType::HurtBox,
+                (projectile_2.position, projectile_2.screen_side),
+            ) {
+                destroyed[i] = true;
+                destroyed[j] = true;
+            }
+        }
+    }
+
+    let mut hit_stop = None;
+    for (projectile, destroyed) in projectiles.iter().zip(destroyed.iter_mut()) {
+        let defender = &mut fighters[1 - projectile.owner];
+        if *destroyed || defender.player_state.can_take_a_hit() == false {
+            continue;
+        }
+        let touches_defender = any_overlap(
+            projectile.colliders(collider_boxes, move_table),
+            ColliderType::HurtBox,
+            (projectile.position, projectile.screen_side),
+            collider_boxes.frame_colliders(&defender.player_state),
+            ColliderType::HitBox,
+            (defender.position, defender.screen_side),
+        );
+        if touches_defender == false {
+            continue;
+        }
+        let attack = projectile.attack(move_table);
+        if let Some(outcome) = defender.take_strike(attack) {
+            *destroyed = true;
+            hit_stop = hit_stop.max(Some(attack.hitstop));
+            fighters[projectile.owner]
+                .meter
+                .gain(attack.attacker_meter(outcome));
+        }
+    }
+
+    let mut index = 0;
+    projectiles.retain(|_| {
+        let keep = destroyed[index] == false;
+        index += 1;
+        keep
+    });
+    hit_stop
+}
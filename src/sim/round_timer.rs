@@ -0,0 +1,58 @@
+#[derive(Copy, Clone, Debug, Default, Hash)]
+pub struct RoundTimer {
+    total_round_timer: usize,
+    round_counter: usize,
+}
+
+impl RoundTimer {
+    pub fn new(round_time_in_seconds: usize) -> RoundTimer {
+        RoundTimer {
+            round_counter: round_time_in_seconds * 60,
+            total_round_timer: round_time_in_seconds * 60,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.round_counter = self.total_round_timer;
+    }
+
+    pub fn tick(&mut self) {
+        if self.round_counter > 0 {
+            self.round_counter -= 1;
+        }
+    }
+
+    pub fn seconds_remaining(&self) -> usize {
+        self.round_counter / 60
+    }
+
+    pub fn finished(&self) -> bool {
+        self.round_counter == 0
+    }
+}
+
+//Freezes both fighters for as long as the move that landed asks for
+#[derive(Copy, Clone, Debug, Default, Hash)]
+pub struct HitStopTimer {
+    frames_left: usize,
+}
+
+impl HitStopTimer {
+    pub fn start(&mut self, length: usize) {
+        self.frames_left = length;
+    }
+
+    pub fn tick(&mut self) {
+        if self.frames_left > 0 {
+            self.frames_left -= 1;
+        }
+    }
+
+    pub fn running(&self) -> bool {
+        self.frames_left > 0
+    }
+
+    pub fn reset(&mut self) {
+        self.frames_left = 0;
+    }
+}
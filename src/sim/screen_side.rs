@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+#[derive(PartialEq, Copy, Clone, Debug, Hash, Reflect, Component)]
+#[reflect(Hash)]
+pub enum ScreenSideEnum {
+    Left,
+    Right,
+}
+
+impl ScreenSideEnum {
+    pub fn back_direction(&self) -> i32 {
+        match self {
+            ScreenSideEnum::Left => {
+                return -1;
+            }
+            ScreenSideEnum::Right => {
+                return 1;
+            }
+        }
+    }
+}
+
+impl Default for ScreenSideEnum {
+    fn default() -> ScreenSideEnum {
+        ScreenSideEnum::Left
+    }
+}
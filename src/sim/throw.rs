@@ -0,0 +1,144 @@
+use crate::sim::*;
+
+//How many frames after being grabbed the defender has to break the throw
+pub const THROW_TECH_FRAMES: usize = 7;
+//A broken throw pushes both fighters apart this fast, in pixels per frame
+const THROW_TECH_PUSHBACK: i32 = 8;
+//And leaves both of them unable to act for this long
+const THROW_TECH_STUN_FRAMES: usize = 12;
+
+impl InputHistory {
+    //Light and medium together, one of them may already be held as long as the other is pressed this frame
+    pub fn pressed_throw(&self) -> bool {
+        (self.pressed_this_frame(InputButton::LightAttack) && self.held(InputButton::MediumAttack))
+            || (self.pressed_this_frame(InputButton::MediumAttack)
+                && self.held(InputButton::LightAttack))
+    }
+}
+
+impl PlayerState {
+    pub fn is_throwing(&self) -> bool {
+        self.player_state == PlayerStateEnum::Throw
+            || self.player_state == PlayerStateEnum::ThrowHold
+    }
+
+    //Anyone standing on the ground who is not already reeling from something, guarding does not help
+    pub fn can_be_thrown(&self) -> bool {
+        self.player_state == PlayerStateEnum::Idle
+            || self.player_state == PlayerStateEnum::Run
+            || self.player_state == PlayerStateEnum::Block
+            || self.player_state == PlayerStateEnum::Crouch
+            || self.player_state == PlayerStateEnum::CrouchBlock
+            || self.player_state == PlayerStateEnum::Throw
+            || self.is_attacking()
+    }
+
+    pub fn break_throw(&mut self) {
+        self.block_stun_timer = AbilityTimer::new(THROW_TECH_STUN_FRAMES);
+        self.pushback_speed = THROW_TECH_PUSHBACK * SUBPIXELS_PER_PIXEL;
+        self.set_player_state_to_transition(PlayerStateEnum::BlockStun);
+    }
+}
+
+impl Fighter {
+    //If the active frames of our throw have a throw box over the other fighter's body
+    fn throw_connects(
+        &self,
+        collider_boxes: &ColliderSetComponent,
+        move_table: &MoveTable,
+        defender: &Fighter,
+    ) -> bool {
+        if self.player_state.player_state != PlayerStateEnum::Throw
+            || self.player_state.move_connected
+            || move_table.active_move(&self.player_state).is_none()
+            || defender.player_state.can_be_thrown() == false
+            || defender.is_airborne()
+        {
+            return false;
+        }
+
+        let throw_boxes = collider_boxes.frame_colliders(&self.player_state);
+        let body_boxes = collider_boxes.frame_colliders(&defender.player_state);
+        throw_boxes
+            .iter()
+            .filter(|collider| collider.collider_type == ColliderType::ThrowBox)
+            .any(|throw_box| {
+                body_boxes
+                    .iter()
+                    .filter(|collider| collider.collider_type == ColliderType::HitBox)
+                    .any(|body_box| {
+                        throw_box.overlaps(
+                            throw_box.center(self.position, self.screen_side),
+                            body_box,
+                            body_box.center(defender.position, defender.screen_side),
+                        )
+                    })
+            })
+    }
+
+    fn grab(&mut self, defender: &mut Fighter) {
+        self.player_state.move_connected = true;
+        self.player_state
+            .set_player_state_to_transition(PlayerStateEnum::ThrowHold);
+        defender
+            .player_state
+            .set_player_state_to_transition(PlayerStateEnum::Thrown);
+    }
+}
+
+//Throws are checked before strikes, returns true if either fighter got hold of the other.
+//Two throws landing on the same frame break each other
+pub fn resolve_grabs(
+    collider_boxes: &ColliderSetComponent,
+    move_table: &MoveTable,
+    fighter_1: &mut Fighter,
+    fighter_2: &mut Fighter,
+) -> bool {
+    let fighter_1_grabs = fighter_1.throw_connects(collider_boxes, move_table, fighter_2);
+    let fighter_2_grabs = fighter_2.throw_connects(collider_boxes, move_table, fighter_1);
+    if fighter_1_grabs && fighter_2_grabs {
+        fighter_1.player_state.break_throw();
+        fighter_2.player_state.break_throw();
+    } else if fighter_1_grabs {
+        fighter_1.grab(fighter_2);
+    } else if fighter_2_grabs {
+        fighter_2.grab(fighter_1);
+    }
+    fighter_1_grabs || fighter_2_grabs
+}
+
+//Once the tech window is over the throw goes through, the two fighters swap sides and the defender is
+//sent flying. Returns how many frames of hit stop that asks for
+pub fn resolve_throw(
+    move_table: &MoveTable,
+    thrower: &mut Fighter,
+    defender: &mut Fighter,
+) -> Option<usize> {
+    if thrower.player_state.player_state != PlayerStateEnum::ThrowHold
+        || defender.player_state.player_state != PlayerStateEnum::Thrown
+    {
+        return None;
+    }
+    if defender.player_state.throw_teched {
+        thrower.player_state.break_throw();
+        defender.player_state.break_throw();
+        return None;
+    }
+    if defender.player_state.throw_tech_timer.running() {
+        return None;
+    }
+
+    let throw = move_table
+        .get(PlayerStateEnum::Throw)
+        .expect("Every character needs a Throw in their move table");
+    std::mem::swap(&mut thrower.position.x, &mut defender.position.x);
+    defender.health.take_damage(throw.damage);
+    defender.meter.gain(throw.meter_to_defender);
+    thrower.meter.gain(throw.attacker_meter(StrikeOutcome::Hit));
+    defender.player_state.take_hit(throw, false);
+    defender.is_shaking = true;
+    thrower
+        .player_state
+        .set_player_state_to_transition(PlayerStateEnum::Idle);
+    Some(throw.hitstop)
+}
@@ -0,0 +1,30 @@
+use crate::*;
+
+//Shows how many hits in a row one player has landed on the other
+#[derive(Default, Copy, Clone, Component)]
+pub struct ComboText {
+    player_id: usize,
+}
+
+impl ComboText {
+    pub fn new(player_id: usize) -> ComboText {
+        ComboText { player_id }
+    }
+}
+
+pub fn combo_counter_system(
+    fight_sim_query: Query<&FightSim>,
+    mut combo_text_query: Query<(&mut Text, &ComboText)>,
+) {
+    for fight_sim in fight_sim_query.iter() {
+        for (mut text, combo_text) in combo_text_query.iter_mut() {
+            //The combo is counted on the fighter taking the hits
+            let hits = fight_sim.players[1 - combo_text.player_id].combo.hits;
+            if hits > 1 {
+                text.sections[0].value = format!("{} Hits", hits);
+            } else {
+                text.sections[0].value = String::new();
+            }
+        }
+    }
+}
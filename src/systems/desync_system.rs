@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+
+use ggrs::MAX_PREDICTION_FRAMES;
+
+use crate::*;
+
+//Checksums are exchanged on their own socket, this far above the GGRS port on both machines
+pub const DESYNC_PORT_OFFSET: u16 = 1000;
+//How many confirmed frames we keep around, so we still have the state to dump once the other side's checksum arrives
+const HISTORY_LENGTH: u32 = 120;
+//The frame followed by the checksum of the FightSim on it, the full state never leaves this machine
+const CHECKSUM_MESSAGE_LENGTH: usize = 12;
+
+#[derive(Default, Copy, Clone, Component)]
+pub struct DesyncText;
+
+//There is no port DESYNC_PORT_OFFSET above this one to exchange checksums on
+#[derive(Debug)]
+pub struct DesyncPortError {
+    pub port: u16,
+}
+
+impl fmt::Display for DesyncPortError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "port {} is too high, desync detection needs the port {} above it as well",
+            self.port, DESYNC_PORT_OFFSET
+        )
+    }
+}
+
+impl Error for DesyncPortError {}
+
+fn desync_port(port: u16) -> Result<u16, DesyncPortError> {
+    port.checked_add(DESYNC_PORT_OFFSET)
+        .ok_or(DesyncPortError { port })
+}
+
+pub struct DesyncDetector {
+    socket: Option<UdpSocket>,
+    remote_addr: Option<SocketAddr>,
+    local_port: u16,
+    newest_frame: u32,
+    last_sent_frame: u32,
+    local_history: BTreeMap<u32, FightSim>,
+    remote_checksums: BTreeMap<u32, u64>,
+    desync_frame: Option<u32>,
+}
+
+impl DesyncDetector {
+    pub fn new(local_port: u16, players: &Vec<String>) -> Result<DesyncDetector, DesyncPortError> {
+        let remote_addr = match players.iter().find(|player| *player != "localhost") {
+            Some(player) => {
+                let mut addr: SocketAddr = player.parse().expect("Invalid remote player address");
+                addr.set_port(desync_port(addr.port())?);
+                Some(addr)
+            }
+            None => None,
+        };
+        let socket = match remote_addr {
+            Some(_) => {
+                let socket = UdpSocket::bind(("0.0.0.0", desync_port(local_port)?))
+                    .expect("Could not bind the desync detection socket");
+                socket
+                    .set_nonblocking(true)
+                    .expect("Could not make the desync detection socket non blocking");
+                Some(socket)
+            }
+            None => None,
+        };
+        Ok(DesyncDetector {
+            socket,
+            remote_addr,
+            local_port,
+            newest_frame: 0,
+            last_sent_frame: 0,
+            local_history: BTreeMap::new(),
+            remote_checksums: BTreeMap::new(),
+            desync_frame: None,
+        })
+    }
+
+    //Called every time the simulation steps, a rollback simply overwrites the frames it resimulates
+    pub fn record(&mut self, fight_sim: &FightSim) {
+        self.newest_frame = fight_sim.frame;
+        self.local_history
+            .insert(fight_sim.frame, fight_sim.clone());
+    }
+
+    pub fn desync_frame(&self) -> Option<u32> {
+        self.desync_frame
+    }
+
+    //The session never predicts more than MAX_PREDICTION_FRAMES past the last frame it has every input for,
+    //so it can no longer roll back anything that far behind the newest frame
+    fn confirmed_frame(&self) -> u32 {
+        self.newest_frame.saturating_sub(MAX_PREDICTION_FRAMES)
+    }
+
+    fn send(&self, message: &[u8]) {
+        if let (Some(socket), Some(remote_addr)) = (&self.socket, self.remote_addr) {
+            //Losing the odd checksum is fine, the next one will catch the same desync
+            if let Err(error) = socket.send_to(message, remote_addr) {
+                println!(
+                    "Could not send a desync checksum to {}: {}",
+                    remote_addr, error
+                );
+            }
+        }
+    }
+
+    fn send_checksums(&mut self) {
+        let confirmed_frame = self.confirmed_frame();
+        while self.last_sent_frame < confirmed_frame {
+            self.last_sent_frame += 1;
+            if let Some(fight_sim) = self.local_history.get(&self.last_sent_frame) {
+                let mut message = Vec::with_capacity(CHECKSUM_MESSAGE_LENGTH);
+                message.extend_from_slice(&self.last_sent_frame.to_le_bytes());
+                message.extend_from_slice(&fight_sim.checksum().to_le_bytes());
+                self.send(&message);
+            }
+        }
+    }
+
+    fn receive(&mut self) {
+        let mut buffer = [0u8; 64];
+        loop {
+            let length = match &self.socket {
+                Some(socket) => match socket.recv_from(&mut buffer) {
+                    Ok((length, _)) => length,
+                    Err(error) if error.kind() == ErrorKind::WouldBlock => return,
+                    Err(error) => {
+                        println!("Could not receive a desync checksum: {}", error);
+                        return;
+                    }
+                },
+                None => return,
+            };
+            if length != CHECKSUM_MESSAGE_LENGTH {
+                continue;
+            }
+            let mut frame_bytes = [0u8; 4];
+            frame_bytes.copy_from_slice(&buffer[0..4]);
+            let mut checksum_bytes = [0u8; 8];
+            checksum_bytes.copy_from_slice(&buffer[4..12]);
+            self.remote_checksums.insert(
+                u32::from_le_bytes(frame_bytes),
+                u64::from_le_bytes(checksum_bytes),
+            );
+        }
+    }
+
+    //Both sides compare every checksum, so each of them notices a desync and dumps its own state
+    fn compare(&mut self) {
+        let confirmed_frame = self.confirmed_frame();
+        let comparable_frames: Vec<u32> = self
+            .remote_checksums
+            .keys()
+            .cloned()
+            .take_while(|frame| *frame <= confirmed_frame)
+            .collect();
+        for frame in comparable_frames {
+            let remote_checksum = self.remote_checksums.remove(&frame).unwrap();
+            let local_checksum = match self.local_history.get(&frame) {
+                Some(fight_sim) => fight_sim.checksum(),
+                None => continue,
+            };
+            if local_checksum != remote_checksum && self.desync_frame.is_none() {
+                println!(
+                    "DESYNC at frame {}: local checksum {:x}, remote checksum {:x}",
+                    frame, local_checksum, remote_checksum
+                );
+                self.desync_frame = Some(frame);
+                self.write_dump(frame);
+            }
+        }
+    }
+
+    //Named after the port as well, so two instances on one machine do not overwrite each other
+    fn write_dump(&self, frame: u32) {
+        if let Some(fight_sim) = self.local_history.get(&frame) {
+            let path = format!("desync_frame_{}_port_{}.txt", frame, self.local_port);
+            match fs::write(&path, format!("{:#?}", fight_sim)) {
+                Ok(()) => println!("DESYNC at frame {}, local state written to {}", frame, path),
+                Err(error) => println!(
+                    "DESYNC at frame {}, could not write the local state to {}: {}",
+                    frame, path, error
+                ),
+            }
+        }
+    }
+
+    fn prune(&mut self) {
+        let oldest_frame = self.last_sent_frame.saturating_sub(HISTORY_LENGTH);
+        self.local_history.retain(|frame, _| *frame >= oldest_frame);
+    }
+}
+
+//Swap checksums of every confirmed frame with the other peer, and shout if they ever disagree
+pub fn desync_detection_system(
+    mut desync_detector: ResMut<DesyncDetector>,
+    mut desync_text_query: Query<&mut Text, With<DesyncText>>,
+) {
+    desync_detector.send_checksums();
+    desync_detector.receive();
+    desync_detector.compare();
+    desync_detector.prune();
+
+    if let Some(frame) = desync_detector.desync_frame() {
+        for mut text in desync_text_query.iter_mut() {
+            text.sections[0].value = format!("DESYNC at frame {}", frame);
+        }
+    }
+}
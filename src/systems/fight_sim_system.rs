@@ -0,0 +1,47 @@
+use crate::*;
+use bevy::app::AppExit;
+
+//The first input from the other player this build could not read. The sim stops stepping as soon as it is set,
+//and input_format_check_system ends the match from outside of rollback
+#[derive(Default)]
+pub struct InputFormatCheck {
+    pub error: Option<InputFormatError>,
+}
+
+//The only system inside of the rollback schedule, everything that is game logic lives in FightSim
+pub fn fight_sim_system(
+    inputs: Res<Vec<GameInput>>,
+    collider_boxes: Res<ColliderSetComponent>,
+    move_table: Res<MoveTable>,
+    mut desync_detector: ResMut<DesyncDetector>,
+    mut input_recorder: ResMut<InputRecorder>,
+    mut input_format_check: ResMut<InputFormatCheck>,
+    mut query: Query<&mut FightSim>,
+) {
+    let frame_inputs = match (
+        InputEvents::from_input_vector(&inputs, 0),
+        InputEvents::from_input_vector(&inputs, 1),
+    ) {
+        (Ok(player_1_input), Ok(player_2_input)) => [player_1_input, player_2_input],
+        (Err(error), _) | (_, Err(error)) => {
+            input_format_check.error.get_or_insert(error);
+            return;
+        }
+    };
+    for mut fight_sim in query.iter_mut() {
+        fight_sim.step(&collider_boxes, &move_table, frame_inputs);
+        desync_detector.record(&fight_sim);
+        input_recorder.record(fight_sim.frame, frame_inputs);
+    }
+}
+
+//GGRS has no way to compare builds before the session starts, so the first input from a mismatched build is where we find out
+pub fn input_format_check_system(
+    input_format_check: Res<InputFormatCheck>,
+    mut app_exit_events: EventWriter<AppExit>,
+) {
+    if let Some(error) = input_format_check.error {
+        println!("Can not play against the other player: {}", error);
+        app_exit_events.send(AppExit);
+    }
+}
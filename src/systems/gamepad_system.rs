@@ -0,0 +1,86 @@
+use crate::*;
+use bevy::input::gamepad::{GamepadEvent, GamepadEventType};
+use bevy::prelude::*;
+
+//Which plugged in gamepad, if any, each local player is currently using
+#[derive(Default)]
+pub struct GamepadAssignments {
+    connected: Vec<Gamepad>,
+    assigned: Vec<Option<Gamepad>>,
+}
+
+impl GamepadAssignments {
+    pub fn for_local_player(&self, local_player_index: usize) -> Option<Gamepad> {
+        self.assigned.get(local_player_index).cloned().flatten()
+    }
+
+    //Pinned pads only ever go to their player, everyone else keeps the pad they already have
+    //and only an empty slot is given the oldest pad nobody is using or has pinned
+    fn reassign(&mut self, input_bindings: &InputBindings, num_local_players: usize) {
+        let pinned: Vec<Option<Gamepad>> = (0..num_local_players)
+            .map(|local_player_index| {
+                let gamepad_bindings = &input_bindings.for_local_player(local_player_index).gamepad;
+                gamepad_bindings.id.map(Gamepad)
+            })
+            .collect();
+        self.assigned.resize(num_local_players, None);
+
+        //A pad that was unplugged only leaves the player who had it
+        for assigned_gamepad in self.assigned.iter_mut() {
+            if let Some(gamepad) = assigned_gamepad {
+                if !self.connected.contains(gamepad) {
+                    *assigned_gamepad = None;
+                }
+            }
+        }
+
+        for (local_player_index, pinned_gamepad) in pinned.iter().enumerate() {
+            if self.assigned[local_player_index].is_some() {
+                continue;
+            }
+            let next_gamepad = match pinned_gamepad {
+                Some(gamepad) => Some(*gamepad).filter(|gamepad| self.connected.contains(gamepad)),
+                None => self
+                    .connected
+                    .iter()
+                    .find(|gamepad| {
+                        !pinned.contains(&Some(**gamepad))
+                            && !self.assigned.contains(&Some(**gamepad))
+                    })
+                    .cloned(),
+            };
+            self.assigned[local_player_index] = next_gamepad;
+        }
+    }
+}
+
+//Pads can come and go at any point, even mid match, so keep the assignments up to date as they do
+pub fn gamepad_connection_system(
+    mut gamepad_events: EventReader<GamepadEvent>,
+    mut gamepad_assignments: ResMut<GamepadAssignments>,
+    input_bindings: Res<InputBindings>,
+    local_id: Res<LocalId>,
+) {
+    let mut changed = false;
+    for GamepadEvent(gamepad, event_type) in gamepad_events.iter() {
+        match event_type {
+            GamepadEventType::Connected => {
+                if !gamepad_assignments.connected.contains(gamepad) {
+                    gamepad_assignments.connected.push(*gamepad);
+                }
+                changed = true;
+            }
+            GamepadEventType::Disconnected => {
+                gamepad_assignments
+                    .connected
+                    .retain(|connected| connected != gamepad);
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    if changed {
+        let num_local_players = local_id.handles.len().max(1);
+        gamepad_assignments.reassign(&input_bindings, num_local_players);
+    }
+}
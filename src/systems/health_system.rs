@@ -1,73 +1,73 @@
-use crate::systems::*;
 use crate::*;
 
 pub const HEALTH_UI_HEIGHT: f32 = 300.0f32;
-#[derive(Default, Component)]
-pub struct PlayerHealth {
-    pub health: usize,
-}
-
-impl PlayerHealth {
-    pub fn new() -> PlayerHealth {
-        PlayerHealth { health: 100 }
-    }
-
-    pub fn reset(&mut self) {
-        self.health = 100;
-    }
-
-    pub fn take_damage(&mut self, amount: usize) -> bool {
-        if amount > self.health {
-            self.health = 0;
-            return true;
-        } else {
-            self.health -= amount;
-            return false;
-        }
-    }
-}
+//The meter bar sits just under the health bar, and is this wide when full
+pub const METER_UI_HEIGHT: f32 = HEALTH_UI_HEIGHT - 30.0f32;
+pub const METER_UI_WIDTH: f32 = 300.0f32;
 
 #[derive(Default, Copy, Clone, Component)]
 pub struct PlayerHealthUI {
-    entity: Option<Entity>,
+    player_id: usize,
 }
 
 impl PlayerHealthUI {
-    pub fn new(entity: Entity) -> PlayerHealthUI {
-        PlayerHealthUI {
-            entity: Some(entity),
-        }
+    pub fn new(player_id: usize) -> PlayerHealthUI {
+        PlayerHealthUI { player_id }
     }
 }
 
 pub fn health_system_ui(
-    mut state: ResMut<State<GameState>>,
+    fight_sim_query: Query<&FightSim>,
     mut health_query: Query<(&mut Transform, &PlayerHealthUI)>,
-    mut players_query: Query<(&PlayerHealth, &mut PlayerState, &ScreenSideEnum)>,
 ) {
-    let mut someone_died = false;
-    for (mut transform, &health_ui) in health_query.iter_mut() {
-        let (player_health, mut player_state, &screen_side) =
-            players_query.get_mut(health_ui.entity.unwrap()).unwrap();
-        transform.scale.x = player_health.health as f32 * 4.0f32;
-        match screen_side {
-            ScreenSideEnum::Left => {
-                transform.translation.x =
-                    -400.0 - ((100.0 - player_health.health as f32) / 2.0f32) * 4.0f32;
-            }
-            ScreenSideEnum::Right => {
-                transform.translation.x =
-                    400.0 + ((100.0 - player_health.health as f32) / 2.0f32) * 4.0f32;
+    for fight_sim in fight_sim_query.iter() {
+        for (mut transform, &health_ui) in health_query.iter_mut() {
+            let fighter = &fight_sim.players[health_ui.player_id];
+            let player_health = fighter.health;
+            transform.scale.x = player_health.health as f32 * 4.0f32;
+            match fighter.screen_side {
+                ScreenSideEnum::Left => {
+                    transform.translation.x =
+                        -400.0 - ((100.0 - player_health.health as f32) / 2.0f32) * 4.0f32;
+                }
+                ScreenSideEnum::Right => {
+                    transform.translation.x =
+                        400.0 + ((100.0 - player_health.health as f32) / 2.0f32) * 4.0f32;
+                }
             }
         }
+    }
+}
 
-        if player_health.health == 0 {
-            player_state.set_player_state_to_transition(PlayerStateEnum::Death);
-            someone_died = true;
-        }
+#[derive(Default, Copy, Clone, Component)]
+pub struct PlayerMeterUI {
+    player_id: usize,
+}
+
+impl PlayerMeterUI {
+    pub fn new(player_id: usize) -> PlayerMeterUI {
+        PlayerMeterUI { player_id }
     }
+}
 
-    if someone_died {
-        state.set(GameState::Reset).unwrap();
+//Grows in from the outside edge of the screen, the same side as the health bar above it
+pub fn meter_system_ui(
+    fight_sim_query: Query<&FightSim>,
+    mut meter_query: Query<(&mut Transform, &PlayerMeterUI)>,
+) {
+    for fight_sim in fight_sim_query.iter() {
+        for (mut transform, &meter_ui) in meter_query.iter_mut() {
+            let fighter = &fight_sim.players[meter_ui.player_id];
+            let width = fighter.meter.meter as f32 / MAX_METER as f32 * METER_UI_WIDTH;
+            transform.scale.x = width;
+            match fighter.screen_side {
+                ScreenSideEnum::Left => {
+                    transform.translation.x = -600.0 + width / 2.0f32;
+                }
+                ScreenSideEnum::Right => {
+                    transform.translation.x = 600.0 - width / 2.0f32;
+                }
+            }
+        }
     }
 }
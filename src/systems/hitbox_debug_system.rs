@@ -46,8 +46,8 @@ pub fn hitbox_debug_system(
     should_render_hit_box: ResMut<ShouldRenderHitBoxes>,
     collider_set_component: Res<ColliderSetComponent>,
     texture_handles: ResMut<TextureAtlasDictionary>,
-    mut debug_query: Query<(&mut Transform, &DebugBox, Entity), Without<PlayerState>>,
-    player_query: Query<(&PlayerState, &Transform, &ScreenSideEnum), Without<DebugBox>>,
+    mut debug_query: Query<(&mut Transform, &DebugBox, Entity)>,
+    fight_sim_query: Query<&FightSim>,
 ) {
     if should_render_hit_box.should_render {
         // move all of the current hit boxes away from the middle of the screen, not great but EH
@@ -57,34 +57,28 @@ pub fn hitbox_debug_system(
 
         let mut debug_iter = debug_query.iter_mut();
 
-        for (&player_state, &player_transform, &screen_side) in player_query.iter() {
-            let frame_colliders = &collider_set_component.colliders
-                [&player_state.player_state.to_string()][player_state.current_sprite_index];
-            for collider in frame_colliders {
-                let (mut transform, &_debug_box, entity) = debug_iter.next().unwrap();
-                let texture_handle;
-                match collider.collider_type {
-                    ColliderType::HitBox => {
-                        texture_handle = texture_handles.debug_hit_box_texture.clone();
+        for fight_sim in fight_sim_query.iter() {
+            for fighter in fight_sim.players.iter() {
+                let frame_colliders = collider_set_component.frame_colliders(&fighter.player_state);
+                for collider in frame_colliders {
+                    let (mut transform, &_debug_box, entity) = debug_iter.next().unwrap();
+                    let texture_handle;
+                    match collider.collider_type {
+                        ColliderType::HitBox => {
+                            texture_handle = texture_handles.debug_hit_box_texture.clone();
+                        }
+                        ColliderType::HurtBox | ColliderType::ThrowBox => {
+                            texture_handle = texture_handles.debug_hurt_box_texture.clone();
+                        }
                     }
-                    ColliderType::HurtBox => {
-                        texture_handle = texture_handles.debug_hurt_box_texture.clone();
-                    }
-                }
-                let mut right_side_inverse = 1.0f32;
-                match screen_side {
-                    ScreenSideEnum::Right => {
-                        right_side_inverse = -1.0f32;
-                    }
-                    _ => {}
+                    let center = collider.center(fighter.position, fighter.screen_side);
+                    transform.translation.x = from_fixed(center.x);
+                    transform.translation.y = from_fixed(center.y);
+                    transform.translation.z = 0.0;
+                    transform.scale.x = from_fixed(collider.width);
+                    transform.scale.y = from_fixed(collider.height);
+                    commands.entity(entity).insert(texture_handle);
                 }
-
-                let mut collider_offset = collider.offset.clone();
-                collider_offset.x = collider_offset.x * right_side_inverse;
-                transform.translation = collider_offset + player_transform.translation;
-                transform.scale.x = collider.dimension.x;
-                transform.scale.y = collider.dimension.y;
-                commands.entity(entity).insert(texture_handle);
             }
         }
     }
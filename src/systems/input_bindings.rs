@@ -0,0 +1,612 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::input::gamepad::GamepadButtonType;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::*;
+
+//How far a stick has to be pushed before it counts as a direction, unless the bindings file says otherwise
+pub const DEFAULT_STICK_DEADZONE: f32 = 0.5;
+
+//How one local players keys are written in the bindings file, each action can have any number of keys
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlayerBindingsDefinition {
+    left: Vec<String>,
+    right: Vec<String>,
+    up: Vec<String>,
+    down: Vec<String>,
+    light_attack: Vec<String>,
+    medium_attack: Vec<String>,
+    heavy_attack: Vec<String>,
+    special_ability: Vec<String>,
+    dash: Vec<String>,
+    #[serde(default)]
+    socd: SocdResolution,
+    #[serde(default)]
+    gamepad: GamepadBindingsDefinition,
+}
+
+//Directions always come from the d-pad and the left stick, only the buttons can be moved around
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, default)]
+struct GamepadBindingsDefinition {
+    id: Option<usize>,
+    deadzone: f32,
+    light_attack: Vec<String>,
+    medium_attack: Vec<String>,
+    heavy_attack: Vec<String>,
+    special_ability: Vec<String>,
+    dash: Vec<String>,
+}
+
+impl Default for GamepadBindingsDefinition {
+    fn default() -> GamepadBindingsDefinition {
+        GamepadBindingsDefinition {
+            id: None,
+            deadzone: DEFAULT_STICK_DEADZONE,
+            light_attack: vec![String::from("West")],
+            medium_attack: vec![String::from("North")],
+            heavy_attack: vec![String::from("RightTrigger")],
+            special_ability: vec![String::from("East")],
+            dash: vec![String::from("South")],
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InputBindingsDefinition {
+    players: Vec<PlayerBindingsDefinition>,
+}
+
+//Which keys drive each part of InputEvents for one local player
+#[derive(Clone, Default)]
+pub struct KeyBindings {
+    pub left: Vec<KeyCode>,
+    pub right: Vec<KeyCode>,
+    pub up: Vec<KeyCode>,
+    pub down: Vec<KeyCode>,
+    pub light_attack: Vec<KeyCode>,
+    pub medium_attack: Vec<KeyCode>,
+    pub heavy_attack: Vec<KeyCode>,
+    pub special_ability: Vec<KeyCode>,
+    pub dash: Vec<KeyCode>,
+}
+
+//Which gamepad buttons drive each part of InputEvents for one local player
+#[derive(Clone, Default)]
+pub struct GamepadBindings {
+    //Pin this player to one gamepad, otherwise they get whichever free pad is plugged in first
+    pub id: Option<usize>,
+    pub deadzone: f32,
+    pub light_attack: Vec<GamepadButtonType>,
+    pub medium_attack: Vec<GamepadButtonType>,
+    pub heavy_attack: Vec<GamepadButtonType>,
+    pub special_ability: Vec<GamepadButtonType>,
+    pub dash: Vec<GamepadButtonType>,
+}
+
+#[derive(Clone, Default)]
+pub struct PlayerBindings {
+    pub keys: KeyBindings,
+    pub gamepad: GamepadBindings,
+    pub socd: SocdResolution,
+}
+
+pub fn any_pressed(keyboard_input: &Input<KeyCode>, keys: &[KeyCode]) -> bool {
+    keys.iter().any(|key| keyboard_input.pressed(*key))
+}
+
+pub fn any_button_pressed(
+    gamepad_buttons: &Input<GamepadButton>,
+    gamepad: Gamepad,
+    buttons: &[GamepadButtonType],
+) -> bool {
+    buttons
+        .iter()
+        .any(|button| gamepad_buttons.pressed(GamepadButton(gamepad, *button)))
+}
+
+#[derive(Debug)]
+pub enum BindingsError {
+    Read(PathBuf, std::io::Error),
+    Parse(PathBuf, serde_json::Error),
+    UnknownKey {
+        player: usize,
+        action: &'static str,
+        key: String,
+    },
+    DuplicateKey {
+        key: String,
+        first_player: usize,
+        first_action: &'static str,
+        second_player: usize,
+        second_action: &'static str,
+    },
+    UnknownButton {
+        player: usize,
+        action: &'static str,
+        button: String,
+    },
+    DuplicateButton {
+        player: usize,
+        button: String,
+        first_action: &'static str,
+        second_action: &'static str,
+    },
+    InvalidDeadzone {
+        player: usize,
+        deadzone: f32,
+    },
+    NotEnoughPlayers {
+        needed: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingsError::Read(path, error) => {
+                write!(
+                    f,
+                    "could not read bindings file {}: {}",
+                    path.display(),
+                    error
+                )
+            }
+            BindingsError::Parse(path, error) => {
+                write!(
+                    f,
+                    "bindings file {} is malformed: {}",
+                    path.display(),
+                    error
+                )
+            }
+            BindingsError::UnknownKey {
+                player,
+                action,
+                key,
+            } => write!(
+                f,
+                "player {} binds {} to \"{}\", which is not a key we know about",
+                player + 1,
+                action,
+                key
+            ),
+            BindingsError::DuplicateKey {
+                key,
+                first_player,
+                first_action,
+                second_player,
+                second_action,
+            } => write!(
+                f,
+                "\"{}\" is bound to both player {} {} and player {} {}",
+                key,
+                first_player + 1,
+                first_action,
+                second_player + 1,
+                second_action
+            ),
+            BindingsError::UnknownButton {
+                player,
+                action,
+                button,
+            } => write!(
+                f,
+                "player {} binds {} to \"{}\", which is not a gamepad button we know about",
+                player + 1,
+                action,
+                button
+            ),
+            BindingsError::DuplicateButton {
+                player,
+                button,
+                first_action,
+                second_action,
+            } => write!(
+                f,
+                "player {} binds gamepad button \"{}\" to both {} and {}",
+                player + 1,
+                button,
+                first_action,
+                second_action
+            ),
+            BindingsError::InvalidDeadzone { player, deadzone } => write!(
+                f,
+                "player {} has a stick deadzone of {}, it needs to be between 0 and 1",
+                player + 1,
+                deadzone
+            ),
+            BindingsError::NotEnoughPlayers { needed, found } => write!(
+                f,
+                "{} local players need bindings, but the bindings file only has {}",
+                needed, found
+            ),
+        }
+    }
+}
+
+impl Error for BindingsError {}
+
+//The key sets for every local player, handed out in order of LocalId::local_player_index
+#[derive(Clone, Default)]
+pub struct InputBindings {
+    players: Vec<PlayerBindings>,
+}
+
+impl InputBindings {
+    pub fn from_file(path: &Path) -> Result<InputBindings, BindingsError> {
+        let file_contents = fs::read_to_string(path)
+            .map_err(|error| BindingsError::Read(path.to_path_buf(), error))?;
+        let deserialized: InputBindingsDefinition = serde_json::from_str(&file_contents)
+            .map_err(|error| BindingsError::Parse(path.to_path_buf(), error))?;
+
+        //Every key can only do one thing, even across players, as local players share a keyboard
+        let mut bound_keys: HashMap<KeyCode, (usize, &'static str)> = HashMap::new();
+        let mut players = vec![];
+        for (player, definition) in deserialized.players.iter().enumerate() {
+            let keys = KeyBindings {
+                left: parse_keys(player, "left", &definition.left, &mut bound_keys)?,
+                right: parse_keys(player, "right", &definition.right, &mut bound_keys)?,
+                up: parse_keys(player, "up", &definition.up, &mut bound_keys)?,
+                down: parse_keys(player, "down", &definition.down, &mut bound_keys)?,
+                light_attack: parse_keys(
+                    player,
+                    "light_attack",
+                    &definition.light_attack,
+                    &mut bound_keys,
+                )?,
+                medium_attack: parse_keys(
+                    player,
+                    "medium_attack",
+                    &definition.medium_attack,
+                    &mut bound_keys,
+                )?,
+                heavy_attack: parse_keys(
+                    player,
+                    "heavy_attack",
+                    &definition.heavy_attack,
+                    &mut bound_keys,
+                )?,
+                special_ability: parse_keys(
+                    player,
+                    "special_ability",
+                    &definition.special_ability,
+                    &mut bound_keys,
+                )?,
+                dash: parse_keys(player, "dash", &definition.dash, &mut bound_keys)?,
+            };
+            players.push(PlayerBindings {
+                keys,
+                gamepad: parse_gamepad(player, &definition.gamepad)?,
+                socd: definition.socd,
+            });
+        }
+        Ok(InputBindings { players })
+    }
+
+    pub fn require_players(&self, needed: usize) -> Result<(), BindingsError> {
+        if self.players.len() < needed {
+            return Err(BindingsError::NotEnoughPlayers {
+                needed,
+                found: self.players.len(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn for_local_player(&self, local_player_index: usize) -> &PlayerBindings {
+        &self.players[local_player_index]
+    }
+}
+
+fn parse_keys(
+    player: usize,
+    action: &'static str,
+    names: &Vec<String>,
+    bound_keys: &mut HashMap<KeyCode, (usize, &'static str)>,
+) -> Result<Vec<KeyCode>, BindingsError> {
+    let mut keys = vec![];
+    for name in names {
+        let key = key_code_from_name(name).ok_or_else(|| BindingsError::UnknownKey {
+            player,
+            action,
+            key: name.clone(),
+        })?;
+        if let Some((first_player, first_action)) = bound_keys.get(&key) {
+            return Err(BindingsError::DuplicateKey {
+                key: name.clone(),
+                first_player: *first_player,
+                first_action: *first_action,
+                second_player: player,
+                second_action: action,
+            });
+        }
+        bound_keys.insert(key, (player, action));
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+//Every pad is its own device, so buttons only need to be unique within a player
+fn parse_gamepad(
+    player: usize,
+    definition: &GamepadBindingsDefinition,
+) -> Result<GamepadBindings, BindingsError> {
+    if !(definition.deadzone >= 0.0 && definition.deadzone < 1.0) {
+        return Err(BindingsError::InvalidDeadzone {
+            player,
+            deadzone: definition.deadzone,
+        });
+    }
+    let mut bound_buttons: HashMap<GamepadButtonType, &'static str> = HashMap::new();
+    let mut parse = |action: &'static str, names: &Vec<String>| {
+        parse_buttons(player, action, names, &mut bound_buttons)
+    };
+    Ok(GamepadBindings {
+        id: definition.id,
+        deadzone: definition.deadzone,
+        light_attack: parse("light_attack", &definition.light_attack)?,
+        medium_attack: parse("medium_attack", &definition.medium_attack)?,
+        heavy_attack: parse("heavy_attack", &definition.heavy_attack)?,
+        special_ability: parse("special_ability", &definition.special_ability)?,
+        dash: parse("dash", &definition.dash)?,
+    })
+}
+
+fn parse_buttons(
+    player: usize,
+    action: &'static str,
+    names: &Vec<String>,
+    bound_buttons: &mut HashMap<GamepadButtonType, &'static str>,
+) -> Result<Vec<GamepadButtonType>, BindingsError> {
+    let mut buttons = vec![];
+    for name in names {
+        let button = button_from_name(name).ok_or_else(|| BindingsError::UnknownButton {
+            player,
+            action,
+            button: name.clone(),
+        })?;
+        if let Some(first_action) = bound_buttons.get(&button) {
+            return Err(BindingsError::DuplicateButton {
+                player,
+                button: name.clone(),
+                first_action: *first_action,
+                second_action: action,
+            });
+        }
+        bound_buttons.insert(button, action);
+        buttons.push(button);
+    }
+    Ok(buttons)
+}
+
+//Keys and buttons are written in the bindings file exactly as their enum variant is named
+macro_rules! names {
+    ($table:ident, $enum:ident, $($variant:ident),* $(,)?) => {
+        const $table: &[(&str, $enum)] = &[$((stringify!($variant), $enum::$variant)),*];
+    };
+}
+
+names!(
+    KEY_NAMES,
+    KeyCode,
+    Key1,
+    Key2,
+    Key3,
+    Key4,
+    Key5,
+    Key6,
+    Key7,
+    Key8,
+    Key9,
+    Key0,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Escape,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Insert,
+    Home,
+    Delete,
+    End,
+    PageDown,
+    PageUp,
+    Left,
+    Up,
+    Right,
+    Down,
+    Back,
+    Return,
+    Space,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+    Apostrophe,
+    Backslash,
+    Comma,
+    Equals,
+    Grave,
+    LAlt,
+    LBracket,
+    LControl,
+    LShift,
+    Minus,
+    Period,
+    RAlt,
+    RBracket,
+    RControl,
+    RShift,
+    Semicolon,
+    Slash,
+    Tab,
+);
+
+pub fn key_code_from_name(name: &str) -> Option<KeyCode> {
+    KEY_NAMES
+        .iter()
+        .find(|(key_name, _)| *key_name == name)
+        .map(|(_, key)| *key)
+}
+
+names!(
+    BUTTON_NAMES,
+    GamepadButtonType,
+    South,
+    East,
+    North,
+    West,
+    C,
+    Z,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+);
+
+pub fn button_from_name(name: &str) -> Option<GamepadButtonType> {
+    BUTTON_NAMES
+        .iter()
+        .find(|(button_name, _)| *button_name == name)
+        .map(|(_, button)| *button)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shipped_bindings() -> serde_json::Value {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/config/bindings.json");
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    //Goes through a real file, so the errors are exactly the ones the game prints on startup
+    fn load(name: &str, bindings: &serde_json::Value) -> Result<InputBindings, BindingsError> {
+        let path = std::env::temp_dir().join(format!("mkp_bindings_{}.json", name));
+        fs::write(&path, bindings.to_string()).unwrap();
+        let loaded = InputBindings::from_file(&path);
+        fs::remove_file(&path).unwrap();
+        loaded
+    }
+
+    #[test]
+    fn shipped_bindings_load() {
+        let input_bindings = load("shipped", &shipped_bindings()).unwrap();
+        assert!(input_bindings.require_players(2).is_ok());
+        assert_eq!(
+            input_bindings.for_local_player(1).keys.left,
+            vec![KeyCode::J]
+        );
+    }
+
+    #[test]
+    fn duplicate_key_names_both_bindings() {
+        let mut bindings = shipped_bindings();
+        bindings["players"][1]["dash"] = serde_json::json!(["Space"]);
+        let error = load("duplicate_key", &bindings).err().unwrap();
+        assert!(matches!(error, BindingsError::DuplicateKey { .. }));
+        assert_eq!(
+            error.to_string(),
+            "\"Space\" is bound to both player 1 dash and player 2 dash"
+        );
+
+        let mut bindings = shipped_bindings();
+        bindings["players"][0]["heavy_attack"] = serde_json::json!(["E", "Q"]);
+        let error = load("duplicate_key_one_player", &bindings).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "\"Q\" is bound to both player 1 light_attack and player 1 heavy_attack"
+        );
+    }
+
+    #[test]
+    fn unknown_key_names_the_player_and_action() {
+        let mut bindings = shipped_bindings();
+        bindings["players"][1]["up"] = serde_json::json!(["Banana"]);
+        let error = load("unknown_key", &bindings).err().unwrap();
+        assert!(matches!(error, BindingsError::UnknownKey { .. }));
+        assert_eq!(
+            error.to_string(),
+            "player 2 binds up to \"Banana\", which is not a key we know about"
+        );
+    }
+
+    #[test]
+    fn unknown_action_is_a_parse_error() {
+        let mut bindings = shipped_bindings();
+        bindings["players"][0]["punch"] = serde_json::json!(["P"]);
+        let error = load("unknown_action", &bindings).err().unwrap();
+        assert!(matches!(error, BindingsError::Parse(..)));
+        let message = error.to_string();
+        assert!(
+            message.starts_with("bindings file ") && message.contains("is malformed"),
+            "{}",
+            message
+        );
+        assert!(message.contains("unknown field `punch`"), "{}", message);
+    }
+}
@@ -0,0 +1,81 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::*;
+
+//Flush the recording to disk this often, so closing the window loses at most a few seconds of it
+const RECORDING_SAVE_INTERVAL: usize = 300;
+
+//Both players inputs for every frame FightSim has stepped, indexed by the frame they were used on
+#[derive(Default)]
+pub struct InputRecorder {
+    path: Option<PathBuf>,
+    frames: Vec<[InputEvents; 2]>,
+    saved_length: usize,
+}
+
+impl InputRecorder {
+    pub fn new(path: Option<PathBuf>) -> InputRecorder {
+        InputRecorder {
+            path,
+            frames: vec![],
+            saved_length: 0,
+        }
+    }
+
+    //A rollback resimulates frames in order, so each one simply overwrites what was predicted before
+    pub fn record(&mut self, frame: u32, inputs: [InputEvents; 2]) {
+        if self.path.is_none() {
+            return;
+        }
+        let index = frame as usize - 1;
+        if index < self.frames.len() {
+            self.frames[index] = inputs;
+        } else {
+            self.frames.push(inputs);
+        }
+    }
+
+    pub fn save(&mut self) {
+        if let Some(path) = &self.path {
+            let contents = serde_json::to_string(&self.frames).unwrap();
+            fs::write(path, contents).expect("Could not write the input recording");
+            self.saved_length = self.frames.len();
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InputReplay {
+    frames: Option<Vec<[InputEvents; 2]>>,
+}
+
+impl InputReplay {
+    pub fn from_file(path: Option<&Path>) -> InputReplay {
+        let frames = path.map(|path| {
+            let file_contents = fs::read_to_string(path).expect("Could not read the input replay");
+            let deserialized: Vec<[InputEvents; 2]> = serde_json::from_str(&file_contents)
+                .expect("The input replay is not a list of input pairs");
+            deserialized
+        });
+        InputReplay { frames }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.frames.is_some()
+    }
+
+    //The input for the frame FightSim is about to step into, None once the recording has run out
+    pub fn input_for(&self, fight_sim: &FightSim, handle: PlayerHandle) -> Option<InputEvents> {
+        self.frames
+            .as_ref()
+            .and_then(|frames| frames.get(fight_sim.frame as usize))
+            .map(|inputs| inputs[handle])
+    }
+}
+
+pub fn input_recording_system(mut input_recorder: ResMut<InputRecorder>) {
+    if input_recorder.frames.len() >= input_recorder.saved_length + RECORDING_SAVE_INTERVAL {
+        input_recorder.save();
+    }
+}
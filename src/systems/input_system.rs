@@ -1,106 +1,246 @@
+use crate::*;
+use bevy::app::AppExit;
 use bevy::prelude::*;
-pub const INPUT_SIZE: usize = std::mem::size_of::<InputEvents>();
-use ggrs::{GameInput, PlayerHandle};
-
-//A Distilation of the true input state
-#[derive(Default, Copy, Clone, Reflect, Hash)]
-pub struct InputEvents {
-    pub left_right_axis: i8,
-    pub up_down_axis: i8,
-    pub jump_was_pressed: bool,
-    pub heavy_attack_was_pressed: bool,
-    pub light_attack_was_pressed: bool,
-    pub medium_attack_was_pressed: bool,
-    pub special_ability: bool,
-    pub dash: bool,
-}
+use ggrs::PlayerHandle;
 
-impl InputEvents {
-    pub fn convert_input_events_into_vector(&self) -> Vec<u8> {
-        let mut vector = vec![0 as u8; std::mem::size_of::<InputEvents>()];
-        vector[0] = self.left_right_axis as u8;
-        vector[1] = self.up_down_axis as u8;
-        vector[2] = self.jump_was_pressed as u8;
-        vector[3] = self.heavy_attack_was_pressed as u8;
-        vector[4] = self.medium_attack_was_pressed as u8;
-        vector[5] = self.light_attack_was_pressed as u8;
-        vector[6] = self.special_ability as u8;
-        vector[7] = self.dash as u8;
-        return vector;
+pub fn local_input_system(
+    handle: In<PlayerHandle>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_assignments: Res<GamepadAssignments>,
+    mut socd_state: ResMut<SocdState>,
+    local_id: Res<LocalId>,
+    input_bindings: Res<InputBindings>,
+    input_replay: Res<InputReplay>,
+    fight_sim_query: Query<&FightSim>,
+    mut app_exit_events: EventWriter<AppExit>,
+) -> Vec<u8> {
+    //When replaying a recording the keyboard and gamepads are ignored entirely, so the run needs nobody at the controls
+    if input_replay.is_active() {
+        for fight_sim in fight_sim_query.iter() {
+            match input_replay.input_for(fight_sim, handle.0) {
+                Some(replayed_input) => {
+                    return replayed_input.convert_input_events_into_vector();
+                }
+                None => {
+                    if handle.0 == 0 {
+                        println!("Replay finished after {} frames", fight_sim.frame);
+                        app_exit_events.send(AppExit);
+                    }
+                }
+            }
+        }
+        return InputEvents::default().convert_input_events_into_vector();
     }
 
-    pub fn from_input_vector(input: &Res<Vec<GameInput>>, player_index: usize) -> InputEvents {
-        InputEvents {
-            left_right_axis: input[player_index].buffer[0] as i8,
-            up_down_axis: input[player_index].buffer[1] as i8,
-            jump_was_pressed: input[player_index].buffer[2] != 0,
-            heavy_attack_was_pressed: input[player_index].buffer[3] != 0,
-            medium_attack_was_pressed: input[player_index].buffer[4] != 0,
-            light_attack_was_pressed: input[player_index].buffer[5] != 0,
-            special_ability: input[player_index].buffer[6] != 0,
-            dash: input[player_index].buffer[7] != 0,
-        }
+    let local_player_index = local_id.local_player_index(handle.0);
+    let bindings = input_bindings.for_local_player(local_player_index);
+    let mut held = HeldInputs::from_keyboard(&keyboard_input, &bindings.keys);
+    if let Some(gamepad) = gamepad_assignments.for_local_player(local_player_index) {
+        held.merge(HeldInputs::from_gamepad(
+            &gamepad_buttons,
+            &gamepad_axes,
+            gamepad,
+            &bindings.gamepad,
+        ));
     }
+    let input_events = held.resolve(
+        socd_state.for_local_player(local_player_index),
+        bindings.socd,
+    );
+
+    return input_events.convert_input_events_into_vector();
 }
 
-pub fn keyboard_input_system(
-    _handle: In<PlayerHandle>,
-    keyboard_input: Res<Input<KeyCode>>,
-    mut input_events: ResMut<InputEvents>,
-) -> Vec<u8> {
-    if keyboard_input.pressed(KeyCode::Left) == false && keyboard_input.pressed(KeyCode::Right) == false {
-        input_events.left_right_axis = 0;
-    }
+//Everything a local player is holding this frame, before opposing directions are resolved
+#[derive(Default)]
+struct HeldInputs {
+    left: bool,
+    right: bool,
+    up: bool,
+    down: bool,
+    light_attack: bool,
+    medium_attack: bool,
+    heavy_attack: bool,
+    special_ability: bool,
+    dash: bool,
+}
 
-    if keyboard_input.pressed(KeyCode::Left) {
-        input_events.left_right_axis = -1;
-    } else if keyboard_input.pressed(KeyCode::Right) {
-        input_events.left_right_axis = 1;
+impl HeldInputs {
+    fn from_keyboard(keyboard_input: &Input<KeyCode>, key_bindings: &KeyBindings) -> HeldInputs {
+        HeldInputs {
+            left: any_pressed(keyboard_input, &key_bindings.left),
+            right: any_pressed(keyboard_input, &key_bindings.right),
+            up: any_pressed(keyboard_input, &key_bindings.up),
+            down: any_pressed(keyboard_input, &key_bindings.down),
+            light_attack: any_pressed(keyboard_input, &key_bindings.light_attack),
+            medium_attack: any_pressed(keyboard_input, &key_bindings.medium_attack),
+            heavy_attack: any_pressed(keyboard_input, &key_bindings.heavy_attack),
+            special_ability: any_pressed(keyboard_input, &key_bindings.special_ability),
+            dash: any_pressed(keyboard_input, &key_bindings.dash),
+        }
     }
 
-    if keyboard_input.pressed(KeyCode::Left) && keyboard_input.pressed(KeyCode::Right) {
-        input_events.left_right_axis = 0;
+    //The d-pad and the left stick both count as directions, so arcade sticks that report either just work
+    fn from_gamepad(
+        gamepad_buttons: &Input<GamepadButton>,
+        gamepad_axes: &Axis<GamepadAxis>,
+        gamepad: Gamepad,
+        gamepad_bindings: &GamepadBindings,
+    ) -> HeldInputs {
+        let button = |button_type| gamepad_buttons.pressed(GamepadButton(gamepad, button_type));
+        let stick = |axis_type| {
+            gamepad_axes
+                .get(GamepadAxis(gamepad, axis_type))
+                .unwrap_or(0.0)
+        };
+        let stick_x = stick(GamepadAxisType::LeftStickX);
+        let stick_y = stick(GamepadAxisType::LeftStickY);
+        let deadzone = gamepad_bindings.deadzone;
+        let pressed =
+            |buttons: &[GamepadButtonType]| any_button_pressed(gamepad_buttons, gamepad, buttons);
+
+        HeldInputs {
+            left: button(GamepadButtonType::DPadLeft) || stick_x < -deadzone,
+            right: button(GamepadButtonType::DPadRight) || stick_x > deadzone,
+            //Sticks report up as positive, InputEvents has up as -1
+            up: button(GamepadButtonType::DPadUp) || stick_y > deadzone,
+            down: button(GamepadButtonType::DPadDown) || stick_y < -deadzone,
+            light_attack: pressed(&gamepad_bindings.light_attack),
+            medium_attack: pressed(&gamepad_bindings.medium_attack),
+            heavy_attack: pressed(&gamepad_bindings.heavy_attack),
+            special_ability: pressed(&gamepad_bindings.special_ability),
+            dash: pressed(&gamepad_bindings.dash),
+        }
     }
 
-    if keyboard_input.pressed(KeyCode::Up) == false && keyboard_input.pressed(KeyCode::Down) == false {
-        input_events.up_down_axis = 0;
+    //Opposing directions are settled here, before they are sent, so every peer sees the same thing
+    fn resolve(&self, press_order: &mut [AxisPressOrder; 2], socd: SocdResolution) -> InputEvents {
+        let [left_right, up_down] = press_order;
+        let up_down_axis = up_down.resolve(self.up, self.down, socd, true);
+        InputEvents {
+            left_right_axis: left_right.resolve(self.left, self.right, socd, false),
+            up_down_axis,
+            jump_was_pressed: up_down_axis == -1,
+            light_attack_was_pressed: self.light_attack,
+            medium_attack_was_pressed: self.medium_attack,
+            heavy_attack_was_pressed: self.heavy_attack,
+            special_ability: self.special_ability,
+            dash: self.dash,
+        }
     }
 
-    if keyboard_input.pressed(KeyCode::Up) {
-        input_events.up_down_axis = -1;
-    } else if keyboard_input.pressed(KeyCode::Down) {
-        input_events.up_down_axis = 1;
+    fn merge(&mut self, other: HeldInputs) {
+        self.left |= other.left;
+        self.right |= other.right;
+        self.up |= other.up;
+        self.down |= other.down;
+        self.light_attack |= other.light_attack;
+        self.medium_attack |= other.medium_attack;
+        self.heavy_attack |= other.heavy_attack;
+        self.special_ability |= other.special_ability;
+        self.dash |= other.dash;
     }
+}
 
-    input_events.jump_was_pressed = false;
-    if keyboard_input.pressed(KeyCode::Up) {
-        input_events.jump_was_pressed = true;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICIES: [SocdResolution; 4] = [
+        SocdResolution::Neutral,
+        SocdResolution::LastInputWins,
+        SocdResolution::FirstInputWins,
+        SocdResolution::UpPriority,
+    ];
+
+    //"L", "R", "U" and "D" for each direction held
+    fn holding(directions: &str) -> HeldInputs {
+        HeldInputs {
+            left: directions.contains('L'),
+            right: directions.contains('R'),
+            up: directions.contains('U'),
+            down: directions.contains('D'),
+            ..HeldInputs::default()
+        }
     }
 
-    input_events.light_attack_was_pressed = false;
-    if keyboard_input.pressed(KeyCode::Q) {
-        input_events.light_attack_was_pressed = true;
+    //Left+Right, then Up+Down, each pressed together, negative side first and positive side first
+    fn press_orders() -> [(&'static str, Vec<HeldInputs>); 6] {
+        [
+            ("left and right together", vec![holding("LR")]),
+            ("left then right", vec![holding("L"), holding("LR")]),
+            ("right then left", vec![holding("R"), holding("LR")]),
+            ("up and down together", vec![holding("UD")]),
+            ("up then down", vec![holding("U"), holding("UD")]),
+            ("down then up", vec![holding("D"), holding("UD")]),
+        ]
     }
 
-    input_events.medium_attack_was_pressed = false;
-    if keyboard_input.pressed(KeyCode::W) {
-        input_events.medium_attack_was_pressed = true;
+    //The axes each entry of press_orders ends up on, left_right_axis then up_down_axis
+    fn expected_axes(socd: SocdResolution) -> [(i8, i8); 6] {
+        match socd {
+            SocdResolution::Neutral => [(0, 0); 6],
+            SocdResolution::LastInputWins => [(0, 0), (1, 0), (-1, 0), (0, 0), (0, 1), (0, -1)],
+            SocdResolution::FirstInputWins => [(0, 0), (-1, 0), (1, 0), (0, 0), (0, -1), (0, 1)],
+            SocdResolution::UpPriority => [(0, 0), (0, 0), (0, 0), (0, -1), (0, -1), (0, -1)],
+        }
     }
 
-    input_events.heavy_attack_was_pressed = false;
-    if keyboard_input.pressed(KeyCode::E) {
-        input_events.heavy_attack_was_pressed = true;
+    fn resolve_frames(frames: &[HeldInputs], socd: SocdResolution) -> InputEvents {
+        let mut press_order = [AxisPressOrder::default(); 2];
+        let mut input_events = InputEvents::default();
+        for held in frames {
+            input_events = held.resolve(&mut press_order, socd);
+        }
+        input_events
     }
 
-    input_events.special_ability = false;
-    if keyboard_input.pressed(KeyCode::R) {
-        input_events.special_ability = true;
+    #[test]
+    fn opposing_directions_resolve_into_the_input_events_we_send() {
+        for socd in POLICIES {
+            for ((press_order, frames), (left_right_axis, up_down_axis)) in
+                press_orders().iter().zip(expected_axes(socd))
+            {
+                let input_events = resolve_frames(frames, socd);
+                let context = format!("{:?} with {}", socd, press_order);
+                assert_eq!(input_events.left_right_axis, left_right_axis, "{}", context);
+                assert_eq!(input_events.up_down_axis, up_down_axis, "{}", context);
+                //Up is what jumps, so it is only pressed when up wins
+                assert_eq!(
+                    input_events.jump_was_pressed,
+                    up_down_axis == -1,
+                    "{}",
+                    context
+                );
+            }
+        }
     }
 
-    input_events.dash = false;
-    if keyboard_input.pressed(KeyCode::Space) {
-        input_events.dash = true;
+    #[test]
+    fn up_priority_jumps_on_up_and_down_however_they_were_pressed() {
+        for (press_order, frames) in press_orders().iter().skip(3) {
+            let input_events = resolve_frames(frames, SocdResolution::UpPriority);
+            assert!(input_events.jump_was_pressed, "{}", press_order);
+        }
     }
 
-    return input_events.convert_input_events_into_vector();
+    #[test]
+    fn buttons_pass_straight_through() {
+        let held = HeldInputs {
+            medium_attack: true,
+            dash: true,
+            ..HeldInputs::default()
+        };
+        let input_events = resolve_frames(&[held], SocdResolution::Neutral);
+        assert_eq!(
+            input_events,
+            InputEvents {
+                medium_attack_was_pressed: true,
+                dash: true,
+                ..InputEvents::default()
+            }
+        );
+    }
 }
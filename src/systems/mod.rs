@@ -1,24 +1,32 @@
-mod cloud_system;
-mod collision_system;
+mod combo_system;
+mod desync_system;
+mod fight_sim_system;
 mod health_system;
+mod gamepad_system;
+mod input_bindings;
+mod input_replay_system;
 mod input_system;
-mod player_movement_system;
-mod player_state_system;
+mod player_render_system;
+mod projectile_system;
 mod restart_system;
 mod round_timer_system;
-mod screen_side_system;
+mod socd;
 mod startup_systems;
 
 mod hitbox_debug_system;
 
-pub use self::cloud_system::*;
-pub use self::collision_system::*;
+pub use self::combo_system::*;
+pub use self::desync_system::*;
+pub use self::fight_sim_system::*;
+pub use self::gamepad_system::*;
 pub use self::health_system::*;
 pub use self::hitbox_debug_system::*;
+pub use self::input_bindings::*;
+pub use self::input_replay_system::*;
 pub use self::input_system::*;
-pub use self::player_movement_system::*;
-pub use self::player_state_system::*;
+pub use self::player_render_system::*;
+pub use self::projectile_system::*;
 pub use self::restart_system::*;
 pub use self::round_timer_system::*;
-pub use self::screen_side_system::*;
+pub use self::socd::*;
 pub use self::startup_systems::*;
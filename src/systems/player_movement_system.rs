@@ -1,77 +0,0 @@
-use crate::systems::*;
-use crate::*;
-use bevy::prelude::*;
-
-const GRAVITY: i32 = 1;
-pub const FLOOR_HEIGHT: f32 = -250.0f32;
-pub const PLAYER_SPEED: i32 = 5;
-pub const PLAYER_DASH_SPEED: i32 = 15;
-pub const PLAYER_LIGHT_HIT_SPEED: i32 = 9;
-pub const PLAYER_MEDIUM_HIT_SPEED: i32 = 12;
-pub const PLAYER_HEAVY_HIT_SPEED: i32 = 15;
-
-pub fn player_movement_system(
-    mut query: Query<(&mut Transform, &mut PlayerState)>,
-    state: Res<State<GameState>>,
-) {
-    if *state.current() == GameState::HitStop {
-        return;
-    }
-    for (mut transform, mut player_state) in query.iter_mut() {
-        transform.translation += Vec3::new(
-            player_state.x_velocity as f32,
-            player_state.y_velocity as f32,
-            0.0,
-        );
-
-        match player_state.player_state {
-            PlayerStateEnum::Run => {}
-            PlayerStateEnum::Dash => {
-                player_state.y_velocity -= GRAVITY;
-                if transform.translation.y < FLOOR_HEIGHT {
-                    player_state.y_velocity = 0;
-                    transform.translation.y = FLOOR_HEIGHT;
-                }
-            }
-            PlayerStateEnum::TakeLightHit |
-            PlayerStateEnum::TakeMediumHit |
-            PlayerStateEnum::TakeHeavyHit => {
-                player_state.y_velocity -= GRAVITY;
-                if transform.translation.y < FLOOR_HEIGHT {
-                    player_state.y_velocity = 0;
-                    transform.translation.y = FLOOR_HEIGHT;
-                }
-            }
-
-            PlayerStateEnum::Jump => {
-                player_state.y_velocity -= GRAVITY;
-                if player_state.y_velocity < 0 {
-                    player_state.set_player_state_to_transition(PlayerStateEnum::Fall);
-                }
-            }
-            PlayerStateEnum::Fall => {
-                player_state.y_velocity -= GRAVITY;
-                if transform.translation.y < FLOOR_HEIGHT {
-                    player_state.set_player_state_to_transition(PlayerStateEnum::Idle);
-                    player_state.y_velocity = 0;
-                    transform.translation.y = FLOOR_HEIGHT;
-                }
-            }
-            PlayerStateEnum::Idle => {
-                if transform.translation.y > FLOOR_HEIGHT {
-                    player_state.set_player_state_to_transition(PlayerStateEnum::Fall);
-                }
-                if player_state.is_colliding == false {
-                    player_state.x_velocity = 0;
-                }
-            }
-            PlayerStateEnum::Death => {
-                if transform.translation.y < FLOOR_HEIGHT {
-                    player_state.y_velocity = 0;
-                    transform.translation.y = FLOOR_HEIGHT;
-                }
-            }
-            _ => {}
-        }
-    }
-}
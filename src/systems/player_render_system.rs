@@ -0,0 +1,61 @@
+use crate::*;
+
+//How far either side of their position a fighter who got hit is drawn during hit stop
+const HIT_STOP_SHAKE_PIXELS: f32 = 2.0;
+
+//Marks the sprite that draws one of the fighters inside of FightSim
+#[derive(Default, Copy, Clone, Component)]
+pub struct PlayerSprite {
+    pub player_id: usize,
+}
+
+impl PlayerSprite {
+    pub fn new(player_id: usize) -> PlayerSprite {
+        PlayerSprite { player_id }
+    }
+}
+
+pub fn player_render_system(
+    texture_atlas_handles: Res<TextureAtlasDictionary>,
+    fight_sim_query: Query<&FightSim>,
+    mut sprite_query: Query<(
+        &PlayerSprite,
+        &mut Transform,
+        &mut TextureAtlasSprite,
+        &mut Handle<TextureAtlas>,
+    )>,
+) {
+    for fight_sim in fight_sim_query.iter() {
+        for (player_sprite, mut transform, mut sprite, mut texture_atlas_handle) in
+            sprite_query.iter_mut()
+        {
+            let fighter = &fight_sim.players[player_sprite.player_id];
+            transform.translation.x = from_fixed(fighter.position.x);
+            if fighter.is_shaking && fight_sim.hit_stop_timer.running() {
+                //Driven by the sim frame, so the shake looks the same after a rollback
+                if fight_sim.frame % 2 == 0 {
+                    transform.translation.x += HIT_STOP_SHAKE_PIXELS;
+                } else {
+                    transform.translation.x -= HIT_STOP_SHAKE_PIXELS;
+                }
+            }
+            transform.translation.y = from_fixed(fighter.position.y);
+
+            match fighter.screen_side {
+                ScreenSideEnum::Left => {
+                    sprite.flip_x = false;
+                }
+                ScreenSideEnum::Right => {
+                    sprite.flip_x = true;
+                }
+            }
+
+            let next_animation = &texture_atlas_handles.animation_handles
+                [&fighter.player_state.player_state.sprite_sheet()];
+            if *texture_atlas_handle != *next_animation {
+                *texture_atlas_handle = next_animation.clone();
+            }
+            sprite.index = fighter.player_state.current_sprite_index as u32;
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::*;
+
+//How big projectile images are drawn compared to the file they come from
+const PROJECTILE_SCALE: f32 = 0.5;
+
+//Draws whatever projectile sits at this index in FightSim
+#[derive(Default, Component)]
+pub struct ProjectileSprite {
+    index: usize,
+}
+
+impl ProjectileSprite {
+    pub fn new(index: usize) -> ProjectileSprite {
+        ProjectileSprite { index }
+    }
+}
+
+//FightSim owns every projectile, so rolling back never leaves one behind.
+//This only keeps one sprite on screen for each of them, spawning and despawning sprites as the count changes
+pub fn projectile_system(
+    mut commands: Commands,
+    local_id: Res<LocalId>,
+    texture_atlas_handles: Res<TextureAtlasDictionary>,
+    move_table: Res<MoveTable>,
+    fight_sim_query: Query<&FightSim>,
+    mut sprites: Query<(
+        &ProjectileSprite,
+        &mut Transform,
+        &mut Handle<ColorMaterial>,
+        Entity,
+    )>,
+) {
+    for fight_sim in fight_sim_query.iter() {
+        for (sprite, mut transform, mut material, entity) in sprites.iter_mut() {
+            match fight_sim.projectiles.get(sprite.index) {
+                Some(projectile) => {
+                    transform.translation.x = from_fixed(projectile.position.x);
+                    transform.translation.y = from_fixed(projectile.position.y);
+                    let animation = &projectile.definition(&move_table).animation;
+                    let image = &texture_atlas_handles.projectile_images[animation];
+                    if *material != *image {
+                        *material = image.clone();
+                    }
+                }
+                None => {
+                    commands.entity(entity).despawn();
+                }
+            }
+        }
+
+        let existing_sprites = sprites.iter().count();
+        for (index, projectile) in fight_sim
+            .projectiles
+            .iter()
+            .enumerate()
+            .skip(existing_sprites)
+        {
+            let x = from_fixed(projectile.position.x);
+            let y = from_fixed(projectile.position.y);
+            //Our own projectiles go behind the fighters, the ones coming at us in front
+            let z = if projectile.owner != local_id.id {
+                1.0f32
+            } else {
+                -1.0f32
+            };
+            let mut new_transform = Transform::from_translation(Vec3::new(x, y, z));
+            new_transform.scale.x *= PROJECTILE_SCALE;
+            new_transform.scale.y *= PROJECTILE_SCALE;
+            let animation = &projectile.definition(&move_table).animation;
+            commands
+                .spawn_bundle(SpriteBundle {
+                    material: texture_atlas_handles.projectile_images[animation].clone(),
+                    transform: new_transform,
+                    ..Default::default()
+                })
+                .insert(ProjectileSprite::new(index));
+        }
+    }
+}
@@ -1,25 +1,9 @@
-use crate::systems::*;
 use crate::*;
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel, Component)]
-pub struct RestartSystem;
-
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Reflect, Component)]
-pub enum RestartSystemStateEnum {
-    Blackout,
-    OpenUp,
-}
-
-impl Default for RestartSystemStateEnum {
-    fn default() -> RestartSystemStateEnum {
-        return RestartSystemStateEnum::Blackout;
-    }
-}
-
-#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Reflect, Component)]
-pub struct RestartSystemState {
-    system_state: RestartSystemStateEnum,
-}
+//How far the blinds move for every frame of the reset, in pixels
+const BLIND_SPEED: f32 = 2.0;
+//Where the blinds sit while the round is being fought, just off screen
+const BLIND_OPEN_Y: f32 = 760.0;
 
 #[derive(Default, Copy, Clone, Component)]
 pub struct UpperBlind {}
@@ -27,110 +11,19 @@ pub struct UpperBlind {}
 #[derive(Default, Copy, Clone, Component)]
 pub struct LowerBlind {}
 
+//The reset itself happens inside of FightSim, this only draws the blinds closing over it and opening again
 pub fn restart_system(
-    mut commands: Commands,
-    mut state: ResMut<State<GameState>>,
-    mut restart_state: ResMut<RestartSystemState>,
-    mut upper_blind_query: Query<
-        (&UpperBlind, &mut Transform),
-        (Without<LowerBlind>, Without<Player1>, Without<Player2>),
-    >,
-    mut lower_blind_query: Query<
-        (&LowerBlind, &mut Transform),
-        (Without<UpperBlind>, Without<Player1>, Without<Player2>),
-    >,
-    mut player_1_restart: Query<
-        (
-            &mut Transform,
-            &mut PlayerState,
-            &mut PlayerHealth,
-            &mut TextureAtlasSprite,
-            &Player1,
-        ),
-        Without<Player2>,
-    >,
-    mut player_2_restart: Query<
-        (
-            &mut Transform,
-            &mut PlayerState,
-            &mut PlayerHealth,
-            &mut TextureAtlasSprite,
-            &Player2,
-        ),
-        Without<Player1>,
-    >,
-    clouds: Query<(&CloudComponent, Entity)>,
-    mut round_timer: Query<&mut RoundTimer>,
+    fight_sim_query: Query<&FightSim>,
+    mut upper_blind_query: Query<(&UpperBlind, &mut Transform), Without<LowerBlind>>,
+    mut lower_blind_query: Query<(&LowerBlind, &mut Transform), Without<UpperBlind>>,
 ) {
-    match restart_state.system_state {
-        RestartSystemStateEnum::Blackout => {
-            let mut slides_in_place = 0;
-            for (_up, mut transform) in lower_blind_query.iter_mut() {
-                transform.translation.y += 2.0f32;
-                if transform.translation.y >= -400.0f32 {
-                    transform.translation.y = -400.0f32;
-                    slides_in_place += 1;
-                }
-            }
-
-            for (_lp, mut transform) in upper_blind_query.iter_mut() {
-                transform.translation.y -= 2.0f32;
-                if transform.translation.y <= 400.0f32 {
-                    transform.translation.y = 400.0f32;
-                    slides_in_place += 1;
-                }
-            }
-
-            if slides_in_place == 2 {
-                restart_state.system_state = RestartSystemStateEnum::OpenUp;
-                for (mut transform, mut player_state, mut player_health, mut sprite, _player1) in
-                    player_1_restart.iter_mut()
-                {
-                    player_state.hard_reset();
-                    player_health.reset();
-                    transform.translation.x = -120.0;
-                    transform.translation.y = FLOOR_HEIGHT;
-                    sprite.index = 0;
-                }
-                for (mut transform, mut player_state, mut player_health, mut sprite, _player2) in
-                    player_2_restart.iter_mut()
-                {
-                    player_state.hard_reset();
-                    player_health.reset();
-                    transform.translation.x = 120.0;
-                    transform.translation.y = FLOOR_HEIGHT;
-                    sprite.index = 0;
-                }
-                for (_cloud, entity) in clouds.iter() {
-                    commands.entity(entity).despawn();
-                }
-
-                for mut round_timer in round_timer.iter_mut() {
-                    round_timer.reset();
-                }
-            }
+    for fight_sim in fight_sim_query.iter() {
+        let offset = fight_sim.blackout_amount() as f32 * BLIND_SPEED;
+        for (_lp, mut transform) in upper_blind_query.iter_mut() {
+            transform.translation.y = BLIND_OPEN_Y - offset;
         }
-        RestartSystemStateEnum::OpenUp => {
-            let mut slides_in_place = 0;
-            for (_up, mut transform) in lower_blind_query.iter_mut() {
-                transform.translation.y -= 2.0f32;
-                if transform.translation.y <= -760.0f32 {
-                    transform.translation.y = -760.0f32;
-                    slides_in_place += 1;
-                }
-            }
-
-            for (_lp, mut transform) in upper_blind_query.iter_mut() {
-                transform.translation.y += 2.0f32;
-                if transform.translation.y >= 760.0f32 {
-                    transform.translation.y = 760.0f32;
-                    slides_in_place += 1;
-                }
-            }
-            if slides_in_place == 2 {
-                restart_state.system_state = RestartSystemStateEnum::Blackout;
-                state.set(GameState::Fighting).unwrap();
-            }
+        for (_up, mut transform) in lower_blind_query.iter_mut() {
+            transform.translation.y = -BLIND_OPEN_Y + offset;
         }
     }
 }
@@ -1,33 +1,16 @@
 use crate::*;
 
-#[derive(Copy, Clone, Default, Component)]
-pub struct RoundTimer {
-    total_round_timer: usize,
-    round_counter: usize,
-}
-
-impl RoundTimer {
-    pub fn new(round_time_in_seconds: usize) -> RoundTimer {
-        RoundTimer {
-            round_counter: round_time_in_seconds * 60,
-            total_round_timer: round_time_in_seconds * 60,
-        }
-    }
-
-    pub fn reset(&mut self) {
-        self.round_counter = self.total_round_timer;
-    }
-}
+#[derive(Default, Copy, Clone, Component)]
+pub struct RoundTimerText;
 
+//Only draws the time left, FightSim decides when the round is over
 pub fn round_timer_system(
-    mut state: ResMut<State<GameState>>,
-    mut timer_query: Query<(&mut RoundTimer, &mut Text)>,
+    fight_sim_query: Query<&FightSim>,
+    mut timer_query: Query<&mut Text, With<RoundTimerText>>,
 ) {
-    for (mut round_timer, mut text) in timer_query.iter_mut() {
-        round_timer.round_counter -= 1;
-        text.sections[0].value = format!("{}", round_timer.round_counter / 60);
-        if round_timer.round_counter == 0 {
-            state.set(GameState::Reset).unwrap();
+    for fight_sim in fight_sim_query.iter() {
+        for mut text in timer_query.iter_mut() {
+            text.sections[0].value = format!("{}", fight_sim.round_timer.seconds_remaining());
         }
     }
 }
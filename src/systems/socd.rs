@@ -0,0 +1,208 @@
+use serde::Deserialize;
+
+//What a direction resolves to when both of its opposing buttons are held, SOCD for short
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocdResolution {
+    Neutral,
+    LastInputWins,
+    FirstInputWins,
+    //Up+Down is Up, Left+Right is still neutral, like most hitbox style controllers
+    UpPriority,
+}
+
+impl Default for SocdResolution {
+    fn default() -> SocdResolution {
+        SocdResolution::Neutral
+    }
+}
+
+//Remembers which side of one axis went down most recently, the last and first input policies need it
+#[derive(Default, Copy, Clone, Debug)]
+pub struct AxisPressOrder {
+    negative_was_held: bool,
+    positive_was_held: bool,
+    //-1 or 1 for whichever side was pressed last, 0 when both went down on the same frame
+    last_pressed: i8,
+}
+
+impl AxisPressOrder {
+    //Returns -1, 0 or 1, with -1 being left or up to match InputEvents
+    pub fn resolve(
+        &mut self,
+        negative_held: bool,
+        positive_held: bool,
+        resolution: SocdResolution,
+        negative_is_up: bool,
+    ) -> i8 {
+        let negative_pressed = negative_held && !self.negative_was_held;
+        let positive_pressed = positive_held && !self.positive_was_held;
+        if negative_pressed && positive_pressed {
+            self.last_pressed = 0;
+        } else if negative_pressed {
+            self.last_pressed = -1;
+        } else if positive_pressed {
+            self.last_pressed = 1;
+        }
+        self.negative_was_held = negative_held;
+        self.positive_was_held = positive_held;
+
+        match (negative_held, positive_held) {
+            (false, false) => 0,
+            (true, false) => -1,
+            (false, true) => 1,
+            (true, true) => match resolution {
+                SocdResolution::Neutral => 0,
+                SocdResolution::LastInputWins => self.last_pressed,
+                SocdResolution::FirstInputWins => -self.last_pressed,
+                SocdResolution::UpPriority => {
+                    if negative_is_up {
+                        -1
+                    } else {
+                        0
+                    }
+                }
+            },
+        }
+    }
+}
+
+//Press order for both axes of every local player, lives outside of rollback as it only shapes what we send
+#[derive(Default)]
+pub struct SocdState {
+    players: Vec<[AxisPressOrder; 2]>,
+}
+
+impl SocdState {
+    pub fn for_local_player(&mut self, local_player_index: usize) -> &mut [AxisPressOrder; 2] {
+        if self.players.len() <= local_player_index {
+            self.players
+                .resize(local_player_index + 1, [AxisPressOrder::default(); 2]);
+        }
+        &mut self.players[local_player_index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICIES: [SocdResolution; 4] = [
+        SocdResolution::Neutral,
+        SocdResolution::LastInputWins,
+        SocdResolution::FirstInputWins,
+        SocdResolution::UpPriority,
+    ];
+
+    //Feeds one (negative held, positive held) pair per frame and returns what the last frame resolved to
+    fn resolve_frames(
+        frames: &[(bool, bool)],
+        resolution: SocdResolution,
+        negative_is_up: bool,
+    ) -> i8 {
+        let mut press_order = AxisPressOrder::default();
+        let mut resolved = 0;
+        for (negative_held, positive_held) in frames {
+            resolved =
+                press_order.resolve(*negative_held, *positive_held, resolution, negative_is_up);
+        }
+        resolved
+    }
+
+    //What both held resolves to for each policy, given which side went down first, if either did
+    fn expected_when_both_held(
+        resolution: SocdResolution,
+        negative_is_up: bool,
+        first_pressed: i8,
+    ) -> i8 {
+        match resolution {
+            SocdResolution::Neutral => 0,
+            SocdResolution::LastInputWins => -first_pressed,
+            SocdResolution::FirstInputWins => first_pressed,
+            SocdResolution::UpPriority => {
+                if negative_is_up {
+                    -1
+                } else {
+                    0
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resolves_every_combination_on_both_axes() {
+        for negative_is_up in [false, true] {
+            for resolution in POLICIES {
+                let context = format!("{:?} with negative_is_up {}", resolution, negative_is_up);
+                assert_eq!(
+                    resolve_frames(&[(false, false)], resolution, negative_is_up),
+                    0,
+                    "nothing held, {}",
+                    context
+                );
+                assert_eq!(
+                    resolve_frames(&[(true, false)], resolution, negative_is_up),
+                    -1,
+                    "negative held, {}",
+                    context
+                );
+                assert_eq!(
+                    resolve_frames(&[(false, true)], resolution, negative_is_up),
+                    1,
+                    "positive held, {}",
+                    context
+                );
+                assert_eq!(
+                    resolve_frames(&[(true, true)], resolution, negative_is_up),
+                    expected_when_both_held(resolution, negative_is_up, 0),
+                    "both pressed together, {}",
+                    context
+                );
+                assert_eq!(
+                    resolve_frames(&[(true, false), (true, true)], resolution, negative_is_up),
+                    expected_when_both_held(resolution, negative_is_up, -1),
+                    "negative then positive, {}",
+                    context
+                );
+                assert_eq!(
+                    resolve_frames(&[(false, true), (true, true)], resolution, negative_is_up),
+                    expected_when_both_held(resolution, negative_is_up, 1),
+                    "positive then negative, {}",
+                    context
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn up_priority_leaves_left_and_right_neutral() {
+        let frames = [(true, false), (true, true)];
+        assert_eq!(
+            resolve_frames(&frames, SocdResolution::UpPriority, false),
+            0
+        );
+        assert_eq!(
+            resolve_frames(&frames, SocdResolution::UpPriority, true),
+            -1
+        );
+        let frames = [(false, true), (true, true)];
+        assert_eq!(
+            resolve_frames(&frames, SocdResolution::UpPriority, false),
+            0
+        );
+        assert_eq!(
+            resolve_frames(&frames, SocdResolution::UpPriority, true),
+            -1
+        );
+    }
+
+    #[test]
+    fn letting_go_of_one_side_goes_back_to_the_other() {
+        for resolution in POLICIES {
+            let frames = [(true, false), (true, true), (false, true)];
+            assert_eq!(resolve_frames(&frames, resolution, false), 1);
+            let frames = [(false, true), (true, true), (true, false)];
+            assert_eq!(resolve_frames(&frames, resolution, false), -1);
+        }
+    }
+}
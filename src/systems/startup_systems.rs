@@ -31,12 +31,13 @@ pub fn match_setup(
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut texture_atlas_handles: ResMut<TextureAtlasDictionary>,
-    p2p_session: Option<Res<P2PSession>>,
 ) {
     commands.spawn_bundle(UiCameraBundle::default());
 
     let cloud_image = asset_server.load("sprites/Cloud.png");
-    texture_atlas_handles.cloud_image = materials.add(cloud_image.clone().into());
+    texture_atlas_handles
+        .projectile_images
+        .insert(String::from("Cloud"), materials.add(cloud_image.into()));
 
     //Load each of our textures
     // TODO: have this handle different characters, for now it is just the single samurai
@@ -130,10 +131,159 @@ pub fn match_setup(
         200.0,
         5,
     );
-
-    let num_players = p2p_session
-        .map(|s| s.num_players())
-        .expect("No GGRS session found");
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/Block.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        2,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/Crouch.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        4,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/CrouchBlock.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        2,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/CrouchLightAttack.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        3,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/CrouchMediumAttack.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        5,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/CrouchHeavyAttack.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        6,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/Throw.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        3,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/ThrowHold.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        1,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/SpecialAbility.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        3,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/ExSpecialAbility.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        3,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/Super.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        6,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/Knockdown.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        3,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/WakeUp.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        5,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/BackRoll.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        3,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/JumpLightAttack.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        3,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/JumpMediumAttack.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        5,
+    );
+    load_sprite_atlas_into_texture_dictionary(
+        String::from("sprites/JumpHeavyAttack.png"),
+        &asset_server,
+        &mut texture_atlases,
+        &mut texture_atlas_handles,
+        200.0,
+        200.0,
+        6,
+    );
 
     //Spawn the background image, simply fire and forget
     let background_texture_handle = asset_server.load("sprites/background_bar.png");
@@ -181,29 +331,30 @@ pub fn match_setup(
     camera.transform = camera_transform;
     commands.spawn_bundle(camera);
 
-    //Spawn each player
+    //The match itself, this is the only entity that rollback needs to know about
+    let fight_sim = FightSim::new();
+    let num_players = fight_sim.players.len();
+    commands
+        .spawn()
+        .insert(fight_sim)
+        .insert(Rollback::new(rip.next_id()));
+
+    //Spawn the sprites that draw each player
     for i in 0..num_players {
         let mut p1_transform =
-            Transform::from_translation(Vec3::new(-120.0 + (240.0 * i as f32), FLOOR_HEIGHT, 0.0));
+            Transform::from_translation(Vec3::new(-120.0 + (240.0 * i as f32), from_fixed(FLOOR_HEIGHT), 0.0));
         p1_transform.scale.x = 2.0;
         p1_transform.scale.y = 2.0;
 
         if i == 0 {
-            let entity_id = commands
+            commands
                 .spawn_bundle(SpriteSheetBundle {
                     texture_atlas: texture_atlas_handles.animation_handles["sprites/Idle.png"]
                         .clone(),
                     transform: p1_transform,
                     ..Default::default()
                 })
-                .insert(SpriteTimer::new(3))
-                .insert(PlayerState::new(i as usize, PlayerStateEnum::Idle))
-                .insert(Rollback::new(rip.next_id()))
-                .insert(Player1::default())
-                .insert(ScreenSideEnum::Left)
-                .insert(PlayerHealth::new())
-                .id()
-                .clone();
+                .insert(PlayerSprite::new(i as usize));
 
             //Health bar
             let hitbox_texture_handle = asset_server.load("sprites/green.png");
@@ -216,7 +367,7 @@ pub fn match_setup(
                     transform: health_transform,
                     ..Default::default()
                 })
-                .insert(PlayerHealthUI::new(entity_id));
+                .insert(PlayerHealthUI::new(i as usize));
             //Health bar backing to help it pop
             let health_background = asset_server.load("sprites/health_background.png");
             let health_transform =
@@ -228,21 +379,14 @@ pub fn match_setup(
                 ..Default::default()
             });
         } else {
-            let entity_id = commands
+            commands
                 .spawn_bundle(SpriteSheetBundle {
                     texture_atlas: texture_atlas_handles.animation_handles["sprites/Idle.png"]
                         .clone(),
                     transform: p1_transform,
                     ..Default::default()
                 })
-                .insert(SpriteTimer::new(3))
-                .insert(PlayerState::new(i as usize, PlayerStateEnum::Idle))
-                .insert(Rollback::new(rip.next_id()))
-                .insert(Player2::default())
-                .insert(PlayerHealth::new())
-                .insert(ScreenSideEnum::Right)
-                .id()
-                .clone();
+                .insert(PlayerSprite::new(i as usize));
 
             let hitbox_texture_handle = asset_server.load("sprites/green.png");
             let mut health_transform =
@@ -254,7 +398,7 @@ pub fn match_setup(
                     transform: health_transform,
                     ..Default::default()
                 })
-                .insert(PlayerHealthUI::new(entity_id));
+                .insert(PlayerHealthUI::new(i as usize));
             let health_background = asset_server.load("sprites/health_background.png");
             let health_transform =
                 Transform::from_translation(Vec3::new(410.0, HEALTH_UI_HEIGHT - 5.0, 2.0));
@@ -266,6 +410,23 @@ pub fn match_setup(
             });
         }
     }
+    //Meter bars start empty, meter_system_ui sizes them from then on
+    for i in 0..num_players {
+        let meter_texture_handle = asset_server.load("sprites/orange_red.png");
+        let mut meter_transform = Transform::from_translation(Vec3::new(
+            -600.0 + 1200.0 * i as f32,
+            METER_UI_HEIGHT,
+            3.0,
+        ));
+        meter_transform.scale = Vec3::new(0.0, 15.0, 1.0);
+        commands
+            .spawn_bundle(SpriteBundle {
+                material: materials.add(meter_texture_handle.into()),
+                transform: meter_transform,
+                ..Default::default()
+            })
+            .insert(PlayerMeterUI::new(i as usize));
+    }
     state.set(GameState::Fighting).unwrap();
 
     commands
@@ -297,5 +458,68 @@ pub fn match_setup(
             ),
             ..Default::default()
         })
-        .insert(RoundTimer::new(60));
+        .insert(RoundTimerText);
+
+    //One combo counter under each health bar, empty until that player lands a second hit in a row
+    for i in 0..2 {
+        let position = if i == 0 {
+            Rect {
+                top: Val::Px(100.0),
+                left: Val::Px(60.0),
+                ..Default::default()
+            }
+        } else {
+            Rect {
+                top: Val::Px(100.0),
+                right: Val::Px(60.0),
+                ..Default::default()
+            }
+        };
+        commands
+            .spawn_bundle(TextBundle {
+                style: Style {
+                    align_self: AlignSelf::FlexEnd,
+                    position_type: PositionType::Absolute,
+                    position,
+                    ..Default::default()
+                },
+                text: Text::with_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 40.0,
+                        color: Color::YELLOW,
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            })
+            .insert(ComboText::new(i));
+    }
+
+    //Stays empty unless the desync detector finds the peers disagreeing about a frame
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                align_self: AlignSelf::FlexEnd,
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(20.0),
+                    left: Val::Px(20.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 40.0,
+                    color: Color::RED,
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        })
+        .insert(DesyncText);
 }